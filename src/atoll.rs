@@ -1,9 +1,13 @@
-use crate::ClockedDiffComparatorIo;
+use crate::enable::EnabledComparatorIo;
+use crate::latch::{LatchedComparatorIo, SrLatchIo};
+use crate::trim::AuxTrimComparatorIo;
+use crate::{ClockedDiffComparatorIo, InvalidParams, MosKind};
 use atoll::route::GreedyRouter;
-use atoll::{IoBuilder, Tile, TileBuilder};
+use atoll::{Instance, IoBuilder, Tile, TileBuilder};
 use serde::{Deserialize, Serialize};
 use sky130pdk::atoll::{MosLength, NmosTile, NtapTile, PmosTile, PtapTile, Sky130ViaMaker};
 use sky130pdk::Sky130Pdk;
+use substrate::arcstr::{self, ArcStr};
 use substrate::block::Block;
 use substrate::error::Result;
 use substrate::geometry::align::AlignMode;
@@ -11,123 +15,162 @@ use substrate::geometry::bbox::Bbox;
 use substrate::geometry::dir::Dir;
 use substrate::geometry::point::Point;
 use substrate::geometry::rect::Rect;
+use substrate::geometry::side::Side;
 use substrate::geometry::span::Span;
 use substrate::geometry::transform::Translate;
-use substrate::io::layout::{Builder, IoShape};
+use substrate::io::layout::{Builder, IoShape, PortGeometryBuilder};
 use substrate::io::schematic::{Bundle, Node};
-use substrate::io::{InOut, Input, Io, Signal};
+use substrate::io::{Array, ArrayBundle, InOut, Input, Io, Signal};
 use substrate::layout::element::Shape;
 use substrate::layout::{ExportsLayoutData, Layout};
 use substrate::schematic::{CellBuilder, ExportsNestedData, Schematic};
 
-#[derive(Debug, Default, Clone, Io)]
-pub struct TwoFingerMosTileIo {
-    pub sd0: InOut<Signal>,
-    pub sd1: InOut<Signal>,
-    pub sd2: InOut<Signal>,
+#[derive(Debug, Clone, Io)]
+pub struct MosTileIo {
+    /// The source/drain terminals, from left to right.
+    pub sd: InOut<Array<Signal>>,
     pub g: Input<Signal>,
     pub b: InOut<Signal>,
 }
 
-impl TwoFingerMosTileIo {
-    pub fn dgsb(d: Node, g: Node, s: Node, b: Node) -> Bundle<Self> {
+impl MosTileIo {
+    /// Creates a new IO for a tile with `nf` fingers.
+    pub fn new(nf: u32) -> Self {
+        Self {
+            sd: InOut(Array::new(nf as usize + 1, Signal)),
+            g: Default::default(),
+            b: Default::default(),
+        }
+    }
+
+    /// Connects the source/drain terminals of a tile with `nf` fingers alternately
+    /// to `s` and `d`, starting with `s` on the leftmost terminal.
+    ///
+    /// With an odd number of fingers, the rightmost terminal is connected to `d`.
+    pub fn dgsb(nf: u32, d: Node, g: Node, s: Node, b: Node) -> Bundle<Self> {
         Bundle::<Self> {
-            sd0: s,
-            sd1: d,
-            sd2: s,
+            sd: ArrayBundle::new(
+                Signal,
+                (0..=nf).map(|i| if i % 2 == 0 { s } else { d }).collect(),
+            ),
             g,
             b,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
-#[substrate(io = "TwoFingerMosTileIo")]
-struct TwoFingerMosTile {
-    w: i64,
-    l: MosLength,
-    kind: MosTileKind,
+/// A MOS tile with a configurable number of fingers.
+///
+/// Each finger has width `w`. The `nf + 1` source/drain terminals are exposed
+/// as the `sd` array. `nf` must be at least 1.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MosTile {
+    pub w: i64,
+    pub l: MosLength,
+    pub nf: u32,
+    pub kind: MosKind,
 }
 
-impl TwoFingerMosTile {
-    pub fn new(w: i64, l: MosLength, kind: MosTileKind) -> Self {
-        Self { w, l, kind }
+impl MosTile {
+    pub fn new(w: i64, l: MosLength, nf: u32, kind: MosKind) -> Self {
+        Self { w, l, nf, kind }
+    }
+
+    pub fn pmos(w: i64, l: MosLength, nf: u32) -> Self {
+        Self::new(w, l, nf, MosKind::Pmos)
     }
 
-    pub fn pmos(w: i64, l: MosLength) -> Self {
-        Self::new(w, l, MosTileKind::Pmos)
+    pub fn nmos(w: i64, l: MosLength, nf: u32) -> Self {
+        Self::new(w, l, nf, MosKind::Nmos)
     }
 
-    pub fn nmos(w: i64, l: MosLength) -> Self {
-        Self::new(w, l, MosTileKind::Nmos)
+    fn validate(&self) -> std::result::Result<(), InvalidParams> {
+        if self.nf == 0 {
+            return Err(InvalidParams(
+                "a MOS tile must have at least one finger".to_string(),
+            ));
+        }
+        Ok(())
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub enum MosTileKind {
-    Pmos,
-    Nmos,
+impl Block for MosTile {
+    type Io = MosTileIo;
+
+    fn id() -> ArcStr {
+        arcstr::literal!("mos_tile")
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("mos_tile_nf{}", self.nf)
+    }
+
+    fn io(&self) -> Self::Io {
+        MosTileIo::new(self.nf)
+    }
 }
 
-impl ExportsNestedData for TwoFingerMosTile {
+impl ExportsNestedData for MosTile {
     type NestedData = ();
 }
 
-impl Schematic<Sky130Pdk> for TwoFingerMosTile {
+impl Schematic<Sky130Pdk> for MosTile {
     fn schematic(
         &self,
         io: &Bundle<<Self as Block>::Io>,
         cell: &mut CellBuilder<Sky130Pdk>,
     ) -> substrate::error::Result<Self::NestedData> {
+        self.validate()?;
         cell.flatten();
         match self.kind {
-            MosTileKind::Pmos => {
-                let pmos = cell.instantiate(PmosTile::new(self.w, self.l, 2));
+            MosKind::Pmos => {
+                let pmos = cell.instantiate(PmosTile::new(self.w, self.l, self.nf.into()));
                 cell.connect(pmos.io().g, io.g);
                 cell.connect(pmos.io().b, io.b);
-                cell.connect(pmos.io().sd[0], io.sd0);
-                cell.connect(pmos.io().sd[1], io.sd1);
-                cell.connect(pmos.io().sd[2], io.sd2);
+                for i in 0..=self.nf as usize {
+                    cell.connect(pmos.io().sd[i], io.sd[i]);
+                }
             }
-            MosTileKind::Nmos => {
-                let nmos = cell.instantiate(NmosTile::new(self.w, self.l, 2));
+            MosKind::Nmos => {
+                let nmos = cell.instantiate(NmosTile::new(self.w, self.l, self.nf.into()));
                 cell.connect(nmos.io().g, io.g);
                 cell.connect(nmos.io().b, io.b);
-                cell.connect(nmos.io().sd[0], io.sd0);
-                cell.connect(nmos.io().sd[1], io.sd1);
-                cell.connect(nmos.io().sd[2], io.sd2);
+                for i in 0..=self.nf as usize {
+                    cell.connect(nmos.io().sd[i], io.sd[i]);
+                }
             }
         }
         Ok(())
     }
 }
 
-impl ExportsLayoutData for TwoFingerMosTile {
+impl ExportsLayoutData for MosTile {
     type LayoutData = ();
 }
 
-impl Layout<Sky130Pdk> for TwoFingerMosTile {
+impl Layout<Sky130Pdk> for MosTile {
     fn layout(
         &self,
         io: &mut Builder<<Self as Block>::Io>,
         cell: &mut substrate::layout::CellBuilder<Sky130Pdk>,
     ) -> substrate::error::Result<Self::LayoutData> {
+        self.validate()?;
         match self.kind {
-            MosTileKind::Pmos => {
-                let pmos = cell.generate(PmosTile::new(self.w, self.l, 2));
+            MosKind::Pmos => {
+                let pmos = cell.generate(PmosTile::new(self.w, self.l, self.nf.into()));
                 io.g.merge(pmos.io().g);
-                io.sd0.merge(pmos.io().sd[0].clone());
-                io.sd1.merge(pmos.io().sd[1].clone());
-                io.sd2.merge(pmos.io().sd[2].clone());
+                for i in 0..=self.nf as usize {
+                    io.sd[i].merge(pmos.io().sd[i].clone());
+                }
                 io.b.merge(pmos.io().b);
                 cell.draw(pmos)?;
             }
-            MosTileKind::Nmos => {
-                let nmos = cell.generate(NmosTile::new(self.w, self.l, 2));
+            MosKind::Nmos => {
+                let nmos = cell.generate(NmosTile::new(self.w, self.l, self.nf.into()));
                 io.g.merge(nmos.io().g);
-                io.sd0.merge(nmos.io().sd[0].clone());
-                io.sd1.merge(nmos.io().sd[1].clone());
-                io.sd2.merge(nmos.io().sd[2].clone());
+                for i in 0..=self.nf as usize {
+                    io.sd[i].merge(nmos.io().sd[i].clone());
+                }
                 io.b.merge(nmos.io().b);
                 cell.draw(nmos)?;
             }
@@ -136,6 +179,93 @@ impl Layout<Sky130Pdk> for TwoFingerMosTile {
     }
 }
 
+#[derive(Debug, Default, Clone, Io)]
+pub struct GuardRingIo {
+    pub tap: InOut<Signal>,
+}
+
+/// A ring of taps enclosing a region of `width` by `height` LCM units.
+///
+/// NMOS devices are enclosed by a P+ ring and PMOS devices by an N+ ring.
+/// Each side of the ring is [`GuardRing::THICKNESS`] LCM units wide.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "GuardRingIo")]
+pub struct GuardRing {
+    /// The type of the enclosed devices.
+    pub kind: MosKind,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl GuardRing {
+    /// The width of each side of the ring, in LCM units.
+    pub const THICKNESS: i64 = TAP_HEIGHT;
+}
+
+impl ExportsNestedData for GuardRing {
+    type NestedData = ();
+}
+
+impl ExportsLayoutData for GuardRing {
+    type LayoutData = ();
+}
+
+impl Tile<Sky130Pdk> for GuardRing {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let t = Self::THICKNESS;
+        let outer_width = self.width + 2 * t;
+        // Top, bottom, left, and right sides.
+        let sides = [
+            (outer_width, t),
+            (outer_width, t),
+            (t, self.height),
+            (t, self.height),
+        ];
+
+        let kind = match self.kind {
+            MosKind::Nmos => TapKind::Ptap,
+            MosKind::Pmos => TapKind::Ntap,
+        };
+        let [top, mut bot, mut left, mut right] =
+            sides.map(|(w, h)| TapInstance::generate(cell, kind, w, h));
+
+        left.align_rect_mut(top.lcm_bounds(), AlignMode::Left, 0);
+        left.align_rect_mut(top.lcm_bounds(), AlignMode::Beneath, 0);
+        right.align_rect_mut(top.lcm_bounds(), AlignMode::Right, 0);
+        right.align_rect_mut(top.lcm_bounds(), AlignMode::Beneath, 0);
+        bot.align_rect_mut(left.lcm_bounds(), AlignMode::Left, 0);
+        bot.align_rect_mut(left.lcm_bounds(), AlignMode::Beneath, 0);
+
+        for side in [&top, &bot, &left, &right] {
+            side.connect(cell, io.schematic.tap);
+        }
+
+        io.layout.tap.set_primary(top.draw(cell)?);
+        bot.draw(cell)?;
+        left.draw(cell)?;
+        right.draw(cell)?;
+
+        cell.set_top_layer(1);
+        cell.set_router(GreedyRouter);
+        cell.set_via_maker(Sky130ViaMaker);
+
+        Ok(((), ()))
+    }
+}
+
+/// An Atoll StrongARM comparator.
+///
+/// See [`StrongArmInstance`](crate::StrongArmInstance) for a description of
+/// the NMOS-input and PMOS-input variants. In the PMOS-input variant, the
+/// internal clock inverter devices have width `precharge_w` and are placed
+/// in rows directly below the N-tap and above the P-tap.
 #[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
 #[substrate(io = "ClockedDiffComparatorIo")]
 pub struct AtollStrongArmInstance {
@@ -144,6 +274,221 @@ pub struct AtollStrongArmInstance {
     pub inv_nmos_w: i64,
     pub inv_pmos_w: i64,
     pub precharge_w: i64,
+    /// The number of fingers of each input pair device.
+    ///
+    /// Each finger has width `input_pair_w`.
+    pub input_pair_nf: u32,
+    /// The channel length of the tail devices.
+    pub tail_l: MosLength,
+    /// The channel length of the input pair devices.
+    ///
+    /// Also used for the auxiliary pair of an [`AtollAuxTrimStrongArmInstance`],
+    /// so that it matches the input pair.
+    pub input_pair_l: MosLength,
+    /// The channel length of the NMOS and PMOS inverter devices.
+    pub inv_l: MosLength,
+    /// The channel length of the precharge devices and, in the PMOS-input variant,
+    /// of the internal clock inverter devices.
+    pub precharge_l: MosLength,
+    /// The type of the input pair and tail devices.
+    pub input_kind: MosKind,
+    /// The placement of the input pair and tail devices.
+    pub pair_placement: PairPlacement,
+    /// Whether to enclose the input pair and each row of inverter devices in a [`GuardRing`].
+    ///
+    /// Rings around NMOS devices are tied to `vss` and rings around PMOS devices to `vdd`.
+    pub guard_rings: bool,
+    /// Whether to place dummy devices at both ends of each row and between the two
+    /// devices of each differential pair.
+    ///
+    /// Rows whose halves are segments of a single device, such as the tail devices
+    /// and the common-centroid input pair, have no dummy in the middle.
+    ///
+    /// Each dummy is a single finger of the same width and length as the adjacent
+    /// devices, with all of its terminals tied to its body. The dummies are included
+    /// in the schematic view of the tile.
+    pub dummies: bool,
+    /// The additional tap rows between the PMOS and NMOS devices.
+    pub boundary_taps: BoundaryTaps,
+    /// The placement of the pins.
+    ///
+    /// Also used for the ports shared with [`ClockedDiffComparatorIo`] by the
+    /// blocks that wrap an [`AtollStrongArmInstance`].
+    pub pins: ComparatorPins,
+    /// The top routing layer.
+    pub top_layer: Metal,
+    /// The power strap mesh, if any.
+    pub straps: Option<PowerStraps>,
+}
+
+impl Default for AtollStrongArmInstance {
+    /// A minimum-length NMOS-input comparator with two-finger devices,
+    /// automatically placed pins, and no optional layout features.
+    fn default() -> Self {
+        Self {
+            half_tail_w: 1_250,
+            input_pair_w: 4_000,
+            inv_nmos_w: 2_000,
+            inv_pmos_w: 1_000,
+            precharge_w: 1_000,
+            input_pair_nf: 2,
+            tail_l: MosLength::L150,
+            input_pair_l: MosLength::L150,
+            inv_l: MosLength::L150,
+            precharge_l: MosLength::L150,
+            input_kind: MosKind::Nmos,
+            pair_placement: PairPlacement::SideBySide,
+            guard_rings: false,
+            dummies: false,
+            boundary_taps: BoundaryTaps::default(),
+            pins: ComparatorPins::default(),
+            top_layer: Metal::Met2,
+            straps: None,
+        }
+    }
+}
+
+/// Additional tap rows placed between the PMOS and NMOS devices of an
+/// [`AtollStrongArmInstance`].
+///
+/// The N-tap rows are tied to `vdd` and span the width of the PMOS inverter row.
+/// The P-tap rows are tied to `vss` and span the width of the NMOS inverter row.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct BoundaryTaps {
+    /// The number of N-tap rows directly beneath the PMOS devices.
+    pub ntap_rows: u32,
+    /// The number of P-tap rows directly above the NMOS devices.
+    pub ptap_rows: u32,
+}
+
+/// A mesh of alternating VDD and VSS straps on the top two routing layers.
+///
+/// The straps on each layer run in its preferred direction, which is horizontal for
+/// met1, met3, and met5 and vertical for met2 and met4. The first strap on each
+/// layer is a VDD strap at the bottom or left edge of the comparator. The straps are
+/// tied to the tap rows by the router.
+///
+/// Requires a top routing layer of at least met3, so that the straps do not
+/// obstruct the met1 pins, a positive width, and a pitch of at least twice the width.
+/// Edge pins may not be placed on either strap layer.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct PowerStraps {
+    /// The width of each strap, in LCM units.
+    pub width: i64,
+    /// The distance between the starts of adjacent straps, in LCM units.
+    pub pitch: i64,
+}
+
+/// The placement of the pins of a comparator with a [`ClockedDiffComparatorIo`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ComparatorPins {
+    pub clock: PinPlacement,
+    pub input_p: PinPlacement,
+    pub input_n: PinPlacement,
+    pub output_p: PinPlacement,
+    pub output_n: PinPlacement,
+    pub vdd: PinPlacement,
+    pub vss: PinPlacement,
+}
+
+/// The placement of a pin.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum PinPlacement {
+    /// The default placement.
+    ///
+    /// Signal pins are full-width met1 stripes on a track determined by the devices
+    /// connected to them. Supply pins are the top and bottom tap rows.
+    #[default]
+    Auto,
+    /// A pin on an edge of the comparator.
+    Edge(EdgePin),
+}
+
+/// A pin on an edge of a comparator.
+///
+/// The pin extends one LCM unit into the comparator from `edge`.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EdgePin {
+    pub edge: Side,
+    pub layer: PinLayer,
+    /// The LCM track on which the pin is centered, measured along the edge from
+    /// the bottom or left of the comparator.
+    pub track: i64,
+    /// The width of the pin along the edge, in layout units.
+    pub width: i64,
+}
+
+/// A metal layer on which a pin can be drawn.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum PinLayer {
+    Met1,
+    Met2,
+}
+
+impl From<PinLayer> for Metal {
+    fn from(value: PinLayer) -> Self {
+        match value {
+            PinLayer::Met1 => Metal::Met1,
+            PinLayer::Met2 => Metal::Met2,
+        }
+    }
+}
+
+/// A metal routing layer.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Metal {
+    Met1,
+    Met2,
+    Met3,
+    Met4,
+    Met5,
+}
+
+impl Metal {
+    /// The Atoll layer index of this layer, where met1 has index 1.
+    pub fn index(self) -> usize {
+        match self {
+            Metal::Met1 => 1,
+            Metal::Met2 => 2,
+            Metal::Met3 => 3,
+            Metal::Met4 => 4,
+            Metal::Met5 => 5,
+        }
+    }
+
+    /// The layer directly beneath this one, if any.
+    pub fn below(self) -> Option<Metal> {
+        match self {
+            Metal::Met1 => None,
+            Metal::Met2 => Some(Metal::Met1),
+            Metal::Met3 => Some(Metal::Met2),
+            Metal::Met4 => Some(Metal::Met3),
+            Metal::Met5 => Some(Metal::Met4),
+        }
+    }
+
+    /// Whether the preferred routing direction of this layer is horizontal.
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Metal::Met1 | Metal::Met3 | Metal::Met5)
+    }
+}
+
+/// The placement of the input pair and tail devices of an [`AtollStrongArmInstance`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum PairPlacement {
+    /// Each input pair device is placed to the left of its complement.
+    #[default]
+    SideBySide,
+    /// Each input pair device is split into two halves placed in an ABBA pattern,
+    /// so that both devices share a common centroid.
+    ///
+    /// The tail devices are split into four abutting single-finger segments at the
+    /// left end of the row. The tail segments lie directly beneath the input pair
+    /// segments only if `input_pair_nf` is 2; with more fingers, the tail row is
+    /// narrower than the input pair row.
+    ///
+    /// Requires an even `input_pair_nf`; an odd value is rejected with an error.
+    CommonCentroid,
 }
 
 impl ExportsNestedData for AtollStrongArmInstance {
@@ -154,6 +499,774 @@ impl ExportsLayoutData for AtollStrongArmInstance {
     type LayoutData = ();
 }
 
+/// An auxiliary differential pair placed in parallel with the input pair
+/// of an [`AtollStrongArmInstance`].
+struct AuxPair {
+    w: i64,
+    p: Node,
+    n: Node,
+}
+
+/// The devices that power down an [`AtollStrongArmInstance`].
+struct EnableSwitch {
+    w: i64,
+    enable: Node,
+}
+
+/// The role of a row of devices in an Atoll comparator.
+///
+/// Used to find the rows whose gates determine the pin tracks, the rows
+/// enclosed by guard rings, and the rows that need a dummy between their halves.
+/// Where several rows share a role, the topmost one determines the pin track.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RowRole {
+    /// The internal clock inverter devices.
+    Clock,
+    Tail,
+    Input,
+    /// The PMOS inverter or latch devices, whose gates are connected to the outputs.
+    Output,
+    /// The NMOS inverter or latch devices.
+    Inverter,
+    Aux,
+    /// The tail switch and, in the PMOS-input variant, the enable inverter devices.
+    Enable,
+    Other,
+}
+
+/// The nodes of an [`AtollStrongArmInstance`].
+#[derive(Copy, Clone)]
+struct StrongArmNodes {
+    vdd: Node,
+    vss: Node,
+    /// The supply on the input pair side of the comparator.
+    input_rail: Node,
+    /// The supply on the reset side of the comparator.
+    reset_rail: Node,
+    clock: Node,
+    /// The clock driving the gates of the tail and precharge devices, which is
+    /// the output of the internal clock inverter in the PMOS-input variant.
+    tail_clock: Node,
+    tail: Node,
+    /// The source of the tail devices, which is `input_rail` unless a tail switch is placed.
+    tail_source: Node,
+    intn: Node,
+    intp: Node,
+    inp: Node,
+    inn: Node,
+    outp: Node,
+    outn: Node,
+}
+
+/// A row of devices of an Atoll comparator, from left to right,
+/// optionally enclosed in a [`GuardRing`].
+struct Row {
+    role: RowRole,
+    kind: MosKind,
+    devices: Vec<Instance<MosTile>>,
+    /// The index of the first device that is not a dummy.
+    first: usize,
+    ring: Option<Instance<GuardRing>>,
+}
+
+impl Row {
+    /// Generates a row of `tile` devices with the given drain, gate, source, and body
+    /// connections, from left to right.
+    fn generate(
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        role: RowRole,
+        tile: MosTile,
+        conns: &[(Node, Node, Node, Node)],
+    ) -> Self {
+        let devices = conns
+            .iter()
+            .map(|&(d, g, s, b)| {
+                cell.generate_primitive_connected(tile, MosTileIo::dgsb(tile.nf, d, g, s, b))
+            })
+            .collect();
+        Self {
+            role,
+            kind: tile.kind,
+            devices,
+            first: 0,
+            ring: None,
+        }
+    }
+
+    /// The width of the row in LCM units, including its guard ring.
+    fn lcm_width(&self) -> i64 {
+        match &self.ring {
+            Some(ring) => ring.lcm_bounds().width(),
+            None => self.devices_lcm_width(),
+        }
+    }
+
+    /// The total width of the devices of the row in LCM units.
+    fn devices_lcm_width(&self) -> i64 {
+        self.devices
+            .iter()
+            .map(|inst| inst.lcm_bounds().width())
+            .sum()
+    }
+
+    /// Places the row directly beneath `prev`, aligned to its left edge.
+    ///
+    /// Returns the LCM bounds of the row, including its guard ring.
+    fn place_beneath(&mut self, prev: Rect) -> Rect {
+        let bounds = match &mut self.ring {
+            Some(ring) => {
+                ring.align_rect_mut(prev, AlignMode::Left, 0);
+                ring.align_rect_mut(prev, AlignMode::Beneath, 0);
+                let inner = ring.lcm_bounds().expand_all(-GuardRing::THICKNESS);
+                self.devices[0].align_rect_mut(inner, AlignMode::Left, 0);
+                self.devices[0].align_rect_mut(inner, AlignMode::Top, 0);
+                ring.lcm_bounds()
+            }
+            None => {
+                self.devices[0].align_rect_mut(prev, AlignMode::Left, 0);
+                self.devices[0].align_rect_mut(prev, AlignMode::Beneath, 0);
+                self.devices[0].lcm_bounds()
+            }
+        };
+        for j in 1..self.devices.len() {
+            let left = self.devices[j - 1].lcm_bounds();
+            self.devices[j].align_rect_mut(left, AlignMode::Bottom, 0);
+            self.devices[j].align_rect_mut(left, AlignMode::ToTheRight, 0);
+        }
+        bounds
+    }
+
+    /// Draws the row and its guard ring.
+    ///
+    /// Returns the bounding box of the gate of the first device that is not a dummy.
+    fn draw(self, cell: &mut TileBuilder<'_, Sky130Pdk>) -> Result<Rect> {
+        if let Some(ring) = self.ring {
+            cell.draw(ring)?;
+        }
+        let mut gate = None;
+        for (i, inst) in self.devices.into_iter().enumerate() {
+            let inst = cell.draw(inst)?;
+            if i == self.first {
+                gate = inst.layout.io().g.primary.bbox();
+            }
+        }
+        Ok(gate.unwrap())
+    }
+}
+
+/// The placement of the devices of an Atoll comparator.
+struct ComparatorPlacement {
+    lcm_bounds: Rect,
+    /// The LCM tracks of the clock, positive input, negative input, positive output,
+    /// and negative output pins, followed by the tracks of the positive and negative
+    /// auxiliary pair gates if an auxiliary pair was placed and the track of the
+    /// enable pin if enable devices were placed.
+    lcm_tracks: Vec<i64>,
+    vdd: IoShape,
+    vss: IoShape,
+    /// The shapes of the VDD power straps, if any.
+    vdd_straps: Vec<IoShape>,
+    /// The shapes of the VSS power straps, if any.
+    vss_straps: Vec<IoShape>,
+}
+
+/// Returns the index of the first row with the given role.
+fn position(rows: &[Row], role: RowRole) -> usize {
+    rows.iter().position(|row| row.role == role).unwrap()
+}
+
+/// Stacks `rows` from top to bottom between an N-tap row and a P-tap row and draws them.
+///
+/// The rows of `boundary_taps` are placed between the last PMOS row and the first NMOS row.
+///
+/// Returns the LCM bounds of the comparator, the shapes of the N-tap and P-tap rows,
+/// and the role and gate bounding box of each row.
+fn stack_rows(
+    cell: &mut TileBuilder<'_, Sky130Pdk>,
+    vdd: Node,
+    vss: Node,
+    mut rows: Vec<Row>,
+    boundary_taps: BoundaryTaps,
+) -> Result<(Rect, IoShape, IoShape, Vec<(RowRole, Rect)>)> {
+    // Rows may have different widths if the input pair is folded
+    // or enclosed in a guard ring.
+    let widths = rows.iter().map(Row::lcm_width).collect::<Vec<_>>();
+    let last_pmos_row = rows
+        .iter()
+        .rposition(|row| row.kind == MosKind::Pmos)
+        .unwrap();
+
+    let ntap = TapInstance::generate_row(cell, TapKind::Ntap, widths[0]);
+    let mut ptap = TapInstance::generate_row(cell, TapKind::Ptap, *widths.last().unwrap());
+    ntap.connect(cell, vdd);
+    ptap.connect(cell, vss);
+
+    let BoundaryTaps {
+        ntap_rows,
+        ptap_rows,
+    } = boundary_taps;
+    let mut boundary_taps = Vec::new();
+    for (kind, count, lcm_width, node) in [
+        (TapKind::Ntap, ntap_rows, widths[last_pmos_row], vdd),
+        (TapKind::Ptap, ptap_rows, widths[last_pmos_row + 1], vss),
+    ] {
+        for _ in 0..count {
+            let tap = TapInstance::generate_row(cell, kind, lcm_width);
+            tap.connect(cell, node);
+            boundary_taps.push(tap);
+        }
+    }
+
+    let mut prev = ntap.lcm_bounds();
+    for (i, row) in rows.iter_mut().enumerate() {
+        prev = row.place_beneath(prev);
+        if i == last_pmos_row {
+            for tap in &mut boundary_taps {
+                tap.align_rect_mut(prev, AlignMode::Left, 0);
+                tap.align_rect_mut(prev, AlignMode::Beneath, 0);
+                prev = tap.lcm_bounds();
+            }
+        }
+    }
+    ptap.align_rect_mut(prev, AlignMode::Left, 0);
+    ptap.align_rect_mut(prev, AlignMode::Beneath, 0);
+
+    let left = ntap.lcm_bounds().left();
+    let lcm_bounds = Rect::from_spans(
+        Span::new(left, left + widths.iter().max().unwrap()),
+        Span::new(ptap.lcm_bounds().bot(), ntap.lcm_bounds().top()),
+    );
+
+    let vss = ptap.draw(cell)?;
+    let vdd = ntap.draw(cell)?;
+    for tap in boundary_taps {
+        tap.draw(cell)?;
+    }
+    let gates = rows
+        .into_iter()
+        .map(|row| Ok((row.role, row.draw(cell)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((lcm_bounds, vdd, vss, gates))
+}
+
+/// Returns the LCM tracks of the pins of a comparator, in the order of
+/// [`ComparatorPlacement::lcm_tracks`], given the role and gate bounding box of each row.
+///
+/// The clock pin track is taken from the topmost row with role `clock_role`.
+fn pin_tracks(
+    cell: &TileBuilder<'_, Sky130Pdk>,
+    gates: &[(RowRole, Rect)],
+    clock_role: RowRole,
+) -> Vec<i64> {
+    let row_gate = |role| {
+        gates
+            .iter()
+            .find(|(r, _)| *r == role)
+            .map(|(_, gate)| *gate)
+    };
+    let m1slice = cell.layer_stack.slice(0..2);
+
+    let mut lcm_tracks = Vec::new();
+    lcm_tracks.push(
+        m1slice
+            .shrink_to_lcm_units(row_gate(clock_role).unwrap())
+            .unwrap()
+            .bot(),
+    );
+    for gate in [RowRole::Input, RowRole::Output, RowRole::Aux]
+        .into_iter()
+        .filter_map(row_gate)
+    {
+        let bot_track = m1slice.expand_to_lcm_units(gate).bot();
+        lcm_tracks.push(bot_track);
+        lcm_tracks.push(bot_track + 1);
+    }
+    if let Some(gate) = row_gate(RowRole::Enable) {
+        lcm_tracks.push(m1slice.shrink_to_lcm_units(gate).unwrap().bot());
+    }
+    lcm_tracks
+}
+
+/// An Atoll block that routes up to a configurable top layer.
+pub trait TopLayer {
+    /// The top routing layer of the block.
+    fn top_layer(&self) -> Metal;
+}
+
+impl TopLayer for AtollStrongArmInstance {
+    fn top_layer(&self) -> Metal {
+        self.top_layer
+    }
+}
+
+impl TopLayer for AtollDoubleTailInstance {
+    fn top_layer(&self) -> Metal {
+        self.top_layer
+    }
+}
+
+impl TopLayer for AtollSrLatch {
+    fn top_layer(&self) -> Metal {
+        self.top_layer
+    }
+}
+
+/// Configures routing of a comparator up to `top_layer`.
+fn configure_router(cell: &mut TileBuilder<'_, Sky130Pdk>, top_layer: Metal) {
+    cell.set_top_layer(top_layer.index());
+    cell.set_router(GreedyRouter);
+    cell.set_via_maker(Sky130ViaMaker);
+}
+
+impl AtollStrongArmInstance {
+    fn validate(&self) -> std::result::Result<(), InvalidParams> {
+        if self.input_pair_nf == 0 {
+            return Err(InvalidParams(
+                "the input pair devices must have at least one finger".to_string(),
+            ));
+        }
+        if self.pair_placement == PairPlacement::CommonCentroid && self.input_pair_nf % 2 != 0 {
+            return Err(InvalidParams(format!(
+                "common-centroid placement requires an even number of input pair fingers, got {}",
+                self.input_pair_nf
+            )));
+        }
+        Ok(())
+    }
+
+    /// Places and draws the devices and taps of the comparator, connected to `io`.
+    ///
+    /// If `aux_pair` is provided, it is placed in the row directly beneath the input pair.
+    ///
+    /// If `enable` is provided, a tail switch is placed on the supply side of the
+    /// tail devices and a pair of output forcing devices is placed at the opposite
+    /// end of the comparator. With a PMOS input pair, the enable inverter devices are
+    /// placed directly beneath the N-tap and above the P-tap. All enable devices
+    /// have the minimum channel length.
+    fn place(
+        &self,
+        io: &Bundle<ClockedDiffComparatorIo>,
+        aux_pair: Option<AuxPair>,
+        enable: Option<EnableSwitch>,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+    ) -> Result<ComparatorPlacement> {
+        self.validate()?;
+
+        let (input_rail, reset_rail) = match self.input_kind {
+            MosKind::Nmos => (io.vss, io.vdd),
+            MosKind::Pmos => (io.vdd, io.vss),
+        };
+        let nodes = StrongArmNodes {
+            vdd: io.vdd,
+            vss: io.vss,
+            input_rail,
+            reset_rail,
+            clock: io.clock,
+            tail_clock: match self.input_kind {
+                MosKind::Nmos => io.clock,
+                MosKind::Pmos => cell.signal("clockb", Signal),
+            },
+            tail: cell.signal("tail", Signal),
+            tail_source: match enable {
+                Some(_) => cell.signal("tail_source", Signal),
+                None => input_rail,
+            },
+            intn: cell.signal("intn", Signal),
+            intp: cell.signal("intp", Signal),
+            inp: io.input.p,
+            inn: io.input.n,
+            outp: io.output.p,
+            outn: io.output.n,
+        };
+
+        let mut rows = self.generate_rows(cell, &nodes);
+        if let Some(aux_pair) = aux_pair {
+            self.insert_aux_row(cell, &nodes, &mut rows, aux_pair);
+        }
+        if let Some(enable) = enable {
+            self.insert_enable_rows(cell, &nodes, &mut rows, enable);
+        }
+        if self.guard_rings {
+            for row in &mut rows {
+                self.enclose_row(cell, &nodes, row);
+            }
+        }
+
+        let (lcm_bounds, vdd, vss, gates) =
+            stack_rows(cell, nodes.vdd, nodes.vss, rows, self.boundary_taps)?;
+        let clock_role = match self.input_kind {
+            MosKind::Nmos => RowRole::Tail,
+            MosKind::Pmos => RowRole::Clock,
+        };
+
+        Ok(ComparatorPlacement {
+            lcm_bounds,
+            lcm_tracks: pin_tracks(cell, &gates, clock_role),
+            vdd,
+            vss,
+            vdd_straps: Vec::new(),
+            vss_straps: Vec::new(),
+        })
+    }
+
+    /// Generates a row of `tile` devices with the given drain, gate, source, and body
+    /// connections, from left to right.
+    ///
+    /// If `dummies` is set, single-finger dummy devices with all of their terminals tied
+    /// to the body are added at both ends of the row and, unless the halves of the row
+    /// are segments of a single device, in its middle.
+    fn generate_row(
+        &self,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        role: RowRole,
+        tile: MosTile,
+        conns: &[(Node, Node, Node, Node)],
+    ) -> Row {
+        let mut row = Row::generate(cell, role, tile, conns);
+        if self.dummies {
+            let dummy = MosTile { nf: 1, ..tile };
+            let rail = conns[0].3;
+            let middle = match role {
+                RowRole::Tail | RowRole::Clock | RowRole::Enable => None,
+                RowRole::Input if self.pair_placement == PairPlacement::CommonCentroid => None,
+                _ => Some(conns.len() / 2),
+            };
+            for i in [Some(conns.len()), middle, Some(0)].into_iter().flatten() {
+                row.devices.insert(
+                    i,
+                    cell.generate_primitive_connected(
+                        dummy,
+                        MosTileIo::dgsb(1, rail, rail, rail, rail),
+                    ),
+                );
+            }
+            row.first = 1;
+        }
+        row
+    }
+
+    /// Generates the rows of the comparator without auxiliary pair or enable devices,
+    /// from top to bottom.
+    ///
+    /// The PMOS devices are always placed above the NMOS devices,
+    /// with the PMOS inverter row directly above the NMOS inverter row.
+    fn generate_rows(
+        &self,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        nodes: &StrongArmNodes,
+    ) -> Vec<Row> {
+        let input_kind = self.input_kind;
+        let reset_kind = input_kind.complement();
+        let (inv_input_w, inv_reset_w) = match input_kind {
+            MosKind::Nmos => (self.inv_nmos_w, self.inv_pmos_w),
+            MosKind::Pmos => (self.inv_pmos_w, self.inv_nmos_w),
+        };
+
+        let half_tail = MosTile::new(self.half_tail_w, self.tail_l, 2, input_kind);
+        let input_pair = MosTile::new(
+            self.input_pair_w,
+            self.input_pair_l,
+            self.input_pair_nf,
+            input_kind,
+        );
+        let inv_input = MosTile::new(inv_input_w, self.inv_l, 2, input_kind);
+        let inv_reset = MosTile::new(inv_reset_w, self.inv_l, 2, reset_kind);
+        let precharge = MosTile::new(self.precharge_w, self.precharge_l, 2, reset_kind);
+
+        let StrongArmNodes {
+            vdd,
+            vss,
+            input_rail,
+            reset_rail,
+            tail,
+            intn,
+            intp,
+            inp,
+            inn,
+            outp,
+            outn,
+            ..
+        } = *nodes;
+
+        let tail_conns = (tail, nodes.tail_clock, nodes.tail_source, input_rail);
+        let (input_a, input_b) = ((intn, inp, tail, input_rail), (intp, inn, tail, input_rail));
+        let (tail_pair, input_pair) = match self.pair_placement {
+            PairPlacement::SideBySide => (
+                self.generate_row(cell, RowRole::Tail, half_tail, &[tail_conns; 2]),
+                self.generate_row(cell, RowRole::Input, input_pair, &[input_a, input_b]),
+            ),
+            PairPlacement::CommonCentroid => {
+                let quarter_tail = MosTile { nf: 1, ..half_tail };
+                let half_input = MosTile {
+                    nf: self.input_pair_nf / 2,
+                    ..input_pair
+                };
+                (
+                    self.generate_row(cell, RowRole::Tail, quarter_tail, &[tail_conns; 4]),
+                    self.generate_row(
+                        cell,
+                        RowRole::Input,
+                        half_input,
+                        &[input_a, input_b, input_b, input_a],
+                    ),
+                )
+            }
+        };
+        let (inv_input_role, inv_reset_role) = match input_kind {
+            MosKind::Nmos => (RowRole::Inverter, RowRole::Output),
+            MosKind::Pmos => (RowRole::Output, RowRole::Inverter),
+        };
+        let inv_input_pair = self.generate_row(
+            cell,
+            inv_input_role,
+            inv_input,
+            &[
+                (outn, outp, intn, input_rail),
+                (outp, outn, intp, input_rail),
+            ],
+        );
+        let inv_reset_pair = self.generate_row(
+            cell,
+            inv_reset_role,
+            inv_reset,
+            &[
+                (outn, outp, reset_rail, reset_rail),
+                (outp, outn, reset_rail, reset_rail),
+            ],
+        );
+        let precharge_pair_a = self.generate_row(
+            cell,
+            RowRole::Other,
+            precharge,
+            &[
+                (outn, nodes.tail_clock, reset_rail, reset_rail),
+                (outp, nodes.tail_clock, reset_rail, reset_rail),
+            ],
+        );
+        let precharge_pair_b = self.generate_row(
+            cell,
+            RowRole::Other,
+            precharge,
+            &[
+                (intn, nodes.tail_clock, reset_rail, reset_rail),
+                (intp, nodes.tail_clock, reset_rail, reset_rail),
+            ],
+        );
+
+        match input_kind {
+            MosKind::Nmos => vec![
+                precharge_pair_a,
+                precharge_pair_b,
+                inv_reset_pair,
+                inv_input_pair,
+                input_pair,
+                tail_pair,
+            ],
+            MosKind::Pmos => {
+                let clock_inv_pmos = MosTile::pmos(self.precharge_w, self.precharge_l, 2);
+                let clock_inv_nmos = MosTile::nmos(self.precharge_w, self.precharge_l, 2);
+                let clock_inv_pmos_pair = self.generate_row(
+                    cell,
+                    RowRole::Clock,
+                    clock_inv_pmos,
+                    &[(nodes.tail_clock, nodes.clock, vdd, vdd); 2],
+                );
+                let clock_inv_nmos_pair = self.generate_row(
+                    cell,
+                    RowRole::Clock,
+                    clock_inv_nmos,
+                    &[(nodes.tail_clock, nodes.clock, vss, vss); 2],
+                );
+                vec![
+                    clock_inv_pmos_pair,
+                    tail_pair,
+                    input_pair,
+                    inv_input_pair,
+                    inv_reset_pair,
+                    precharge_pair_b,
+                    precharge_pair_a,
+                    clock_inv_nmos_pair,
+                ]
+            }
+        }
+    }
+
+    /// Inserts a row containing the auxiliary pair directly beneath the input pair.
+    fn insert_aux_row(
+        &self,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        nodes: &StrongArmNodes,
+        rows: &mut Vec<Row>,
+        aux_pair: AuxPair,
+    ) {
+        let aux = MosTile::new(aux_pair.w, self.input_pair_l, 2, self.input_kind);
+        let aux_row = self.generate_row(
+            cell,
+            RowRole::Aux,
+            aux,
+            &[
+                (nodes.intn, aux_pair.p, nodes.tail, nodes.input_rail),
+                (nodes.intp, aux_pair.n, nodes.tail, nodes.input_rail),
+            ],
+        );
+        rows.insert(position(rows, RowRole::Input) + 1, aux_row);
+    }
+
+    /// Inserts the rows containing the tail switch, the output forcing devices, and,
+    /// with a PMOS input pair, the enable inverter.
+    fn insert_enable_rows(
+        &self,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        nodes: &StrongArmNodes,
+        rows: &mut Vec<Row>,
+        enable: EnableSwitch,
+    ) {
+        let input_kind = self.input_kind;
+        let StrongArmNodes {
+            vdd,
+            vss,
+            input_rail,
+            reset_rail,
+            tail_source,
+            outp,
+            outn,
+            ..
+        } = *nodes;
+
+        let l = MosLength::L150;
+        let switch = MosTile::new(enable.w, l, 2, input_kind);
+        let force = MosTile::new(enable.w, l, 2, input_kind.complement());
+        let gate = match input_kind {
+            MosKind::Nmos => enable.enable,
+            MosKind::Pmos => cell.signal("enableb", Signal),
+        };
+        let switch_row = self.generate_row(
+            cell,
+            RowRole::Enable,
+            switch,
+            &[(tail_source, gate, input_rail, input_rail); 2],
+        );
+        let force_row = self.generate_row(
+            cell,
+            RowRole::Other,
+            force,
+            &[
+                (outn, gate, reset_rail, reset_rail),
+                (outp, gate, reset_rail, reset_rail),
+            ],
+        );
+        match input_kind {
+            MosKind::Nmos => {
+                rows.insert(0, force_row);
+                rows.push(switch_row);
+            }
+            MosKind::Pmos => {
+                let enable_inv_pmos_pair = self.generate_row(
+                    cell,
+                    RowRole::Enable,
+                    MosTile::pmos(enable.w, l, 2),
+                    &[(gate, enable.enable, vdd, vdd); 2],
+                );
+                let enable_inv_nmos_pair = self.generate_row(
+                    cell,
+                    RowRole::Enable,
+                    MosTile::nmos(enable.w, l, 2),
+                    &[(gate, enable.enable, vss, vss); 2],
+                );
+                // The tail switch is placed between the PMOS clock inverter row and the tail row.
+                rows.insert(position(rows, RowRole::Clock) + 1, switch_row);
+                rows.insert(0, enable_inv_pmos_pair);
+                rows.push(force_row);
+                rows.push(enable_inv_nmos_pair);
+            }
+        }
+    }
+
+    /// Encloses the input pair and inverter rows in a [`GuardRing`].
+    ///
+    /// Other rows are left unchanged.
+    fn enclose_row(
+        &self,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        nodes: &StrongArmNodes,
+        row: &mut Row,
+    ) {
+        let kind = match row.role {
+            RowRole::Input => self.input_kind,
+            RowRole::Output => MosKind::Pmos,
+            RowRole::Inverter => MosKind::Nmos,
+            _ => return,
+        };
+        let ring = cell.generate_primitive(GuardRing {
+            kind,
+            width: row.devices_lcm_width(),
+            height: row.devices[0].lcm_bounds().height(),
+        });
+        cell.connect(
+            ring.io().tap,
+            match kind {
+                MosKind::Nmos => nodes.vss,
+                MosKind::Pmos => nodes.vdd,
+            },
+        );
+        row.ring = Some(ring);
+    }
+
+    /// Configures routing of the comparator up to its top layer and draws its power straps.
+    ///
+    /// `extra_pins` contains the placements of the pins of the comparator beyond those of
+    /// a [`ClockedDiffComparatorIo`]. Returns an error if an edge pin lies on a strap layer.
+    fn configure_routing(
+        &self,
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        placement: &mut ComparatorPlacement,
+        extra_pins: &[PinPlacement],
+        vdd: Node,
+        vss: Node,
+    ) -> Result<()> {
+        configure_router(cell, self.top_layer);
+
+        if let Some(straps) = self.straps {
+            let ComparatorPins {
+                clock,
+                input_p,
+                input_n,
+                output_p,
+                output_n,
+                vdd: vdd_pin,
+                vss: vss_pin,
+            } = self.pins;
+            let strap_layers = [self.top_layer.below(), Some(self.top_layer)];
+            for pin in [
+                clock, input_p, input_n, output_p, output_n, vdd_pin, vss_pin,
+            ]
+            .iter()
+            .chain(extra_pins)
+            {
+                if let PinPlacement::Edge(pin) = pin {
+                    if strap_layers.contains(&Some(pin.layer.into())) {
+                        return Err(InvalidParams(format!(
+                            "edge pin on track {} of the {:?} edge lies on the {:?} power straps",
+                            pin.track,
+                            pin.edge,
+                            Metal::from(pin.layer)
+                        ))
+                        .into());
+                    }
+                }
+            }
+
+            let (vdd_straps, vss_straps) =
+                draw_power_straps(cell, placement.lcm_bounds, self.top_layer, straps, vdd, vss)?;
+            placement.vdd_straps = vdd_straps;
+            placement.vss_straps = vss_straps;
+        }
+
+        Ok(())
+    }
+}
+
 impl Tile<Sky130Pdk> for AtollStrongArmInstance {
     fn tile<'a>(
         &self,
@@ -163,251 +1276,963 @@ impl Tile<Sky130Pdk> for AtollStrongArmInstance {
         <Self as ExportsNestedData>::NestedData,
         <Self as ExportsLayoutData>::LayoutData,
     )> {
-        let l = MosLength::L150;
+        let mut placement = self.place(io.schematic, None, None, cell)?;
+
+        self.configure_routing(
+            cell,
+            &mut placement,
+            &[],
+            io.schematic.vdd,
+            io.schematic.vss,
+        )?;
+
+        draw_comparator_pins(
+            cell,
+            &placement,
+            ComparatorPorts::new(io.schematic, io.layout),
+            &self.pins,
+            [],
+        )?;
+
+        Ok(((), ()))
+    }
+}
+
+/// An Atoll [`AuxTrimStrongArmInstance`](crate::trim::AuxTrimStrongArmInstance).
+///
+/// The auxiliary pair is placed in the row directly beneath the input pair.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "AuxTrimComparatorIo")]
+pub struct AtollAuxTrimStrongArmInstance {
+    pub strongarm: AtollStrongArmInstance,
+    pub aux_pair_w: i64,
+    /// The placement of the positive trim pin.
+    pub trim_p_pin: PinPlacement,
+    /// The placement of the negative trim pin.
+    pub trim_n_pin: PinPlacement,
+}
 
-        let half_tail = TwoFingerMosTile::nmos(self.half_tail_w, l);
-        let input_pair = TwoFingerMosTile::nmos(self.input_pair_w, l);
-        let inv_nmos = TwoFingerMosTile::nmos(self.inv_nmos_w, l);
-        let inv_pmos = TwoFingerMosTile::pmos(self.inv_pmos_w, l);
-        let precharge = TwoFingerMosTile::pmos(self.precharge_w, l);
+impl ExportsNestedData for AtollAuxTrimStrongArmInstance {
+    type NestedData = ();
+}
 
-        let tail = cell.signal("tail", Signal);
+impl ExportsLayoutData for AtollAuxTrimStrongArmInstance {
+    type LayoutData = ();
+}
+
+impl Tile<Sky130Pdk> for AtollAuxTrimStrongArmInstance {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let mut placement = self.strongarm.place(
+            &Bundle::<ClockedDiffComparatorIo> {
+                input: io.schematic.input.clone(),
+                output: io.schematic.output.clone(),
+                clock: io.schematic.clock,
+                vdd: io.schematic.vdd,
+                vss: io.schematic.vss,
+            },
+            Some(AuxPair {
+                w: self.aux_pair_w,
+                p: io.schematic.trim.p,
+                n: io.schematic.trim.n,
+            }),
+            None,
+            cell,
+        )?;
+
+        self.strongarm.configure_routing(
+            cell,
+            &mut placement,
+            &[self.trim_p_pin, self.trim_n_pin],
+            io.schematic.vdd,
+            io.schematic.vss,
+        )?;
+
+        draw_comparator_pins(
+            cell,
+            &placement,
+            ComparatorPorts {
+                clock: (io.schematic.clock, &mut io.layout.clock),
+                input_p: (io.schematic.input.p, &mut io.layout.input.p),
+                input_n: (io.schematic.input.n, &mut io.layout.input.n),
+                output_p: (io.schematic.output.p, &mut io.layout.output.p),
+                output_n: (io.schematic.output.n, &mut io.layout.output.n),
+                vdd: (io.schematic.vdd, &mut io.layout.vdd),
+                vss: (io.schematic.vss, &mut io.layout.vss),
+            },
+            &self.strongarm.pins,
+            [
+                (
+                    (io.schematic.trim.p, &mut io.layout.trim.p),
+                    self.trim_p_pin,
+                ),
+                (
+                    (io.schematic.trim.n, &mut io.layout.trim.n),
+                    self.trim_n_pin,
+                ),
+            ],
+        )?;
+
+        Ok(((), ()))
+    }
+}
+
+/// An Atoll [`EnabledStrongArmInstance`](crate::enable::EnabledStrongArmInstance).
+///
+/// The tail switch is placed on the supply side of the tail devices and the output
+/// forcing devices are placed at the opposite end of the comparator.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "EnabledComparatorIo")]
+pub struct AtollEnabledStrongArmInstance {
+    pub strongarm: AtollStrongArmInstance,
+    pub switch_w: i64,
+    /// The placement of the enable pin.
+    pub enable_pin: PinPlacement,
+}
+
+impl ExportsNestedData for AtollEnabledStrongArmInstance {
+    type NestedData = ();
+}
+
+impl ExportsLayoutData for AtollEnabledStrongArmInstance {
+    type LayoutData = ();
+}
+
+impl Tile<Sky130Pdk> for AtollEnabledStrongArmInstance {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let mut placement = self.strongarm.place(
+            &Bundle::<ClockedDiffComparatorIo> {
+                input: io.schematic.input.clone(),
+                output: io.schematic.output.clone(),
+                clock: io.schematic.clock,
+                vdd: io.schematic.vdd,
+                vss: io.schematic.vss,
+            },
+            None,
+            Some(EnableSwitch {
+                w: self.switch_w,
+                enable: io.schematic.enable,
+            }),
+            cell,
+        )?;
+
+        self.strongarm.configure_routing(
+            cell,
+            &mut placement,
+            &[self.enable_pin],
+            io.schematic.vdd,
+            io.schematic.vss,
+        )?;
+
+        draw_comparator_pins(
+            cell,
+            &placement,
+            ComparatorPorts {
+                clock: (io.schematic.clock, &mut io.layout.clock),
+                input_p: (io.schematic.input.p, &mut io.layout.input.p),
+                input_n: (io.schematic.input.n, &mut io.layout.input.n),
+                output_p: (io.schematic.output.p, &mut io.layout.output.p),
+                output_n: (io.schematic.output.n, &mut io.layout.output.n),
+                vdd: (io.schematic.vdd, &mut io.layout.vdd),
+                vss: (io.schematic.vss, &mut io.layout.vss),
+            },
+            &self.strongarm.pins,
+            [(
+                (io.schematic.enable, &mut io.layout.enable),
+                self.enable_pin,
+            )],
+        )?;
+
+        Ok(((), ()))
+    }
+}
+
+/// An Atoll [`DoubleTailInstance`](crate::DoubleTailInstance).
+///
+/// Each pair of devices is placed in its own row, with the PMOS rows
+/// above the NMOS rows.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "ClockedDiffComparatorIo")]
+pub struct AtollDoubleTailInstance {
+    pub half_preamp_tail_w: i64,
+    pub input_pair_w: i64,
+    pub preamp_reset_w: i64,
+    pub half_latch_tail_w: i64,
+    pub latch_nmos_w: i64,
+    pub latch_pmos_w: i64,
+    pub output_reset_w: i64,
+    pub half_clock_inv_nmos_w: i64,
+    pub half_clock_inv_pmos_w: i64,
+    /// The channel length of the preamplifier and latch tail devices.
+    pub tail_l: MosLength,
+    /// The channel length of the input pair devices.
+    pub input_pair_l: MosLength,
+    /// The channel length of the NMOS and PMOS latch devices.
+    pub latch_l: MosLength,
+    /// The channel length of the preamplifier and output reset devices.
+    pub reset_l: MosLength,
+    /// The channel length of the clock inverter devices.
+    pub clock_inv_l: MosLength,
+    /// The placement of the pins.
+    pub pins: ComparatorPins,
+    /// The top routing layer.
+    pub top_layer: Metal,
+}
+
+impl Default for AtollDoubleTailInstance {
+    /// A minimum-length double-tail comparator with automatically placed pins.
+    fn default() -> Self {
+        Self {
+            half_preamp_tail_w: 1_250,
+            input_pair_w: 4_000,
+            preamp_reset_w: 1_000,
+            half_latch_tail_w: 1_000,
+            latch_nmos_w: 1_000,
+            latch_pmos_w: 2_000,
+            output_reset_w: 1_000,
+            half_clock_inv_nmos_w: 500,
+            half_clock_inv_pmos_w: 1_000,
+            tail_l: MosLength::L150,
+            input_pair_l: MosLength::L150,
+            latch_l: MosLength::L150,
+            reset_l: MosLength::L150,
+            clock_inv_l: MosLength::L150,
+            pins: ComparatorPins::default(),
+            top_layer: Metal::Met2,
+        }
+    }
+}
+
+impl ExportsNestedData for AtollDoubleTailInstance {
+    type NestedData = ();
+}
+
+impl ExportsLayoutData for AtollDoubleTailInstance {
+    type LayoutData = ();
+}
+
+impl Tile<Sky130Pdk> for AtollDoubleTailInstance {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let half_preamp_tail = MosTile::nmos(self.half_preamp_tail_w, self.tail_l, 2);
+        let input_pair = MosTile::nmos(self.input_pair_w, self.input_pair_l, 2);
+        let preamp_reset = MosTile::pmos(self.preamp_reset_w, self.reset_l, 2);
+        let half_latch_tail = MosTile::pmos(self.half_latch_tail_w, self.tail_l, 2);
+        let latch_nmos = MosTile::nmos(self.latch_nmos_w, self.latch_l, 2);
+        let latch_pmos = MosTile::pmos(self.latch_pmos_w, self.latch_l, 2);
+        let output_reset = MosTile::nmos(self.output_reset_w, self.reset_l, 2);
+        let half_clock_inv_nmos = MosTile::nmos(self.half_clock_inv_nmos_w, self.clock_inv_l, 2);
+        let half_clock_inv_pmos = MosTile::pmos(self.half_clock_inv_pmos_w, self.clock_inv_l, 2);
+
+        let clockb = cell.signal("clockb", Signal);
+        let preamp_tail = cell.signal("preamp_tail", Signal);
+        let latch_tail = cell.signal("latch_tail", Signal);
         let intn = cell.signal("intn", Signal);
         let intp = cell.signal("intp", Signal);
 
-        let mut tail_pair = (0..2)
-            .map(|_| {
-                cell.generate_primitive_connected(
-                    half_tail,
-                    TwoFingerMosTileIo::dgsb(
-                        tail,
-                        io.schematic.clock,
-                        io.schematic.vss,
-                        io.schematic.vss,
-                    ),
-                )
-            })
-            .collect::<Vec<_>>();
+        let (vdd, vss) = (io.schematic.vdd, io.schematic.vss);
+        let (inp, inn) = (io.schematic.input.p, io.schematic.input.n);
+        let (outp, outn) = (io.schematic.output.p, io.schematic.output.n);
+        let clock = io.schematic.clock;
+
+        let rows = vec![
+            Row::generate(
+                cell,
+                RowRole::Clock,
+                half_clock_inv_pmos,
+                &[(clockb, clock, vdd, vdd); 2],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Other,
+                half_latch_tail,
+                &[(latch_tail, clockb, vdd, vdd); 2],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Output,
+                latch_pmos,
+                &[(outn, outp, latch_tail, vdd), (outp, outn, latch_tail, vdd)],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Other,
+                preamp_reset,
+                &[(intn, clock, vdd, vdd), (intp, clock, vdd, vdd)],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Inverter,
+                latch_nmos,
+                &[(outn, outp, vss, vss), (outp, outn, vss, vss)],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Other,
+                output_reset,
+                &[(outp, intn, vss, vss), (outn, intp, vss, vss)],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Clock,
+                half_clock_inv_nmos,
+                &[(clockb, clock, vss, vss); 2],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Input,
+                input_pair,
+                &[(intn, inp, preamp_tail, vss), (intp, inn, preamp_tail, vss)],
+            ),
+            Row::generate(
+                cell,
+                RowRole::Tail,
+                half_preamp_tail,
+                &[(preamp_tail, clock, vss, vss); 2],
+            ),
+        ];
+
+        let (lcm_bounds, vdd_tap, vss_tap, gates) =
+            stack_rows(cell, vdd, vss, rows, BoundaryTaps::default())?;
+
+        configure_router(cell, self.top_layer);
+
+        let placement = ComparatorPlacement {
+            lcm_bounds,
+            lcm_tracks: pin_tracks(cell, &gates, RowRole::Tail),
+            vdd: vdd_tap,
+            vss: vss_tap,
+            vdd_straps: Vec::new(),
+            vss_straps: Vec::new(),
+        };
+        draw_comparator_pins(
+            cell,
+            &placement,
+            ComparatorPorts::new(io.schematic, io.layout),
+            &self.pins,
+            [],
+        )?;
+
+        Ok(((), ()))
+    }
+}
+
+/// An Atoll [`SrLatch`](crate::latch::SrLatch).
+///
+/// The PMOS devices of both NAND gates are placed in a single row,
+/// above a row containing the NMOS devices.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "SrLatchIo")]
+pub struct AtollSrLatch {
+    pub nmos_w: i64,
+    pub pmos_w: i64,
+    /// The top routing layer.
+    pub top_layer: Metal,
+}
+
+impl ExportsNestedData for AtollSrLatch {
+    type NestedData = ();
+}
+
+impl ExportsLayoutData for AtollSrLatch {
+    type LayoutData = ();
+}
+
+impl Tile<Sky130Pdk> for AtollSrLatch {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let l = MosLength::L150;
+
+        let nmos = MosTile::nmos(self.nmos_w, l, 2);
+        let pmos = MosTile::pmos(self.pmos_w, l, 2);
+
+        let q_x = cell.signal("q_x", Signal);
+        let qb_x = cell.signal("qb_x", Signal);
+
+        let (vdd, vss) = (io.schematic.vdd, io.schematic.vss);
+        let (sb, rb) = (io.schematic.sb, io.schematic.rb);
+        let (q, qb) = (io.schematic.q, io.schematic.qb);
+
+        let mut pmos_row = [
+            MosTileIo::dgsb(2, q, sb, vdd, vdd),
+            MosTileIo::dgsb(2, q, qb, vdd, vdd),
+            MosTileIo::dgsb(2, qb, rb, vdd, vdd),
+            MosTileIo::dgsb(2, qb, q, vdd, vdd),
+        ]
+        .into_iter()
+        .map(|conns| cell.generate_primitive_connected(pmos, conns))
+        .collect::<Vec<_>>();
+        let mut nmos_row = [
+            MosTileIo::dgsb(2, q, sb, q_x, vss),
+            MosTileIo::dgsb(2, q_x, qb, vss, vss),
+            MosTileIo::dgsb(2, qb, rb, qb_x, vss),
+            MosTileIo::dgsb(2, qb_x, q, vss, vss),
+        ]
+        .into_iter()
+        .map(|conns| cell.generate_primitive_connected(nmos, conns))
+        .collect::<Vec<_>>();
 
-        // todo: get rid of minus 1
         let mut ptap =
-            cell.generate_primitive(PtapTile::new(2 * tail_pair[0].lcm_bounds().width() - 1, 2));
+            TapInstance::generate_row(cell, TapKind::Ptap, 4 * nmos_row[0].lcm_bounds().width());
         let ntap =
-            cell.generate_primitive(NtapTile::new(2 * tail_pair[1].lcm_bounds().width() - 1, 2));
-        cell.connect(ptap.io().vnb, io.schematic.vss);
-        cell.connect(ntap.io().vpb, io.schematic.vdd);
-
-        let mut input_pair = (0..2)
-            .map(|i| {
-                cell.generate_primitive_connected(
-                    input_pair,
-                    TwoFingerMosTileIo::dgsb(
-                        if i == 0 { intn } else { intp },
-                        if i == 0 {
-                            io.schematic.input.p
-                        } else {
-                            io.schematic.input.n
-                        },
-                        tail,
-                        io.schematic.vss,
-                    ),
-                )
-            })
-            .collect::<Vec<_>>();
-        let mut inv_nmos_pair = (0..2)
-            .map(|i| {
-                cell.generate_primitive_connected(
-                    inv_nmos,
-                    if i == 0 {
-                        TwoFingerMosTileIo::dgsb(
-                            io.schematic.output.n,
-                            io.schematic.output.p,
-                            intn,
-                            io.schematic.vss,
-                        )
-                    } else {
-                        TwoFingerMosTileIo::dgsb(
-                            io.schematic.output.p,
-                            io.schematic.output.n,
-                            intp,
-                            io.schematic.vss,
-                        )
-                    },
-                )
-            })
-            .collect::<Vec<_>>();
-        let mut inv_pmos_pair = (0..2)
-            .map(|i| {
-                cell.generate_primitive_connected(
-                    inv_pmos,
-                    TwoFingerMosTileIo::dgsb(
-                        if i == 0 {
-                            io.schematic.output.n
-                        } else {
-                            io.schematic.output.p
-                        },
-                        if i == 0 {
-                            io.schematic.output.p
-                        } else {
-                            io.schematic.output.n
-                        },
-                        io.schematic.vdd,
-                        io.schematic.vdd,
-                    ),
-                )
-            })
-            .collect::<Vec<_>>();
-        let mut precharge_pair_a = (0..2)
-            .map(|i| {
-                cell.generate_primitive_connected(
-                    precharge,
-                    TwoFingerMosTileIo::dgsb(
-                        if i == 0 {
-                            io.schematic.output.n
-                        } else {
-                            io.schematic.output.p
-                        },
-                        io.schematic.clock,
-                        io.schematic.vdd,
-                        io.schematic.vdd,
-                    ),
-                )
-            })
-            .collect::<Vec<_>>();
-        let mut precharge_pair_b = (0..2)
-            .map(|i| {
-                cell.generate_primitive_connected(
-                    precharge,
-                    TwoFingerMosTileIo::dgsb(
-                        if i == 0 { intn } else { intp },
-                        io.schematic.clock,
-                        io.schematic.vdd,
-                        io.schematic.vdd,
-                    ),
-                )
-            })
-            .collect::<Vec<_>>();
+            TapInstance::generate_row(cell, TapKind::Ntap, 4 * pmos_row[0].lcm_bounds().width());
+        ptap.connect(cell, vss);
+        ntap.connect(cell, vdd);
 
         let mut prev = ntap.lcm_bounds();
 
-        for row in [
-            &mut precharge_pair_a,
-            &mut precharge_pair_b,
-            &mut inv_pmos_pair,
-            &mut inv_nmos_pair,
-            &mut input_pair,
-            &mut tail_pair,
-        ] {
+        for row in [&mut pmos_row, &mut nmos_row] {
             row[0].align_rect_mut(prev, AlignMode::Left, 0);
             row[0].align_rect_mut(prev, AlignMode::Beneath, 0);
             prev = row[0].lcm_bounds();
-            row[1].align_rect_mut(prev, AlignMode::Bottom, 0);
-            row[1].align_rect_mut(prev, AlignMode::ToTheRight, 0);
+            for i in 1..row.len() {
+                let left = row[i - 1].lcm_bounds();
+                row[i].align_rect_mut(left, AlignMode::Bottom, 0);
+                row[i].align_rect_mut(left, AlignMode::ToTheRight, 0);
+            }
         }
 
         ptap.align_rect_mut(prev, AlignMode::Left, 0);
         ptap.align_rect_mut(prev, AlignMode::Beneath, 0);
 
-        let strongarm_lcm_hspan = ptap.lcm_bounds().hspan();
-
-        let ptap = cell.draw(ptap)?;
-        let ntap = cell.draw(ntap)?;
-        let tail_pair = tail_pair
-            .into_iter()
-            .map(|inst| cell.draw(inst))
-            .collect::<Result<Vec<_>>>()?;
-        let input_pair = input_pair
-            .into_iter()
-            .map(|inst| cell.draw(inst))
-            .collect::<Result<Vec<_>>>()?;
-        let _inv_nmos_pair = inv_nmos_pair
-            .into_iter()
-            .map(|inst| cell.draw(inst))
-            .collect::<Result<Vec<_>>>()?;
-        let inv_pmos_pair = inv_pmos_pair
+        let vss_tap = ptap.draw(cell)?;
+        let vdd_tap = ntap.draw(cell)?;
+        let pmos_row = pmos_row
             .into_iter()
             .map(|inst| cell.draw(inst))
             .collect::<Result<Vec<_>>>()?;
-        let _precharge_pair_a = precharge_pair_a
-            .into_iter()
-            .map(|inst| cell.draw(inst))
-            .collect::<Result<Vec<_>>>()?;
-        let _precharge_pair_b = precharge_pair_b
+        let _nmos_row = nmos_row
             .into_iter()
             .map(|inst| cell.draw(inst))
             .collect::<Result<Vec<_>>>()?;
 
-        cell.set_top_layer(2);
-        cell.set_router(GreedyRouter);
-        cell.set_via_maker(Sky130ViaMaker);
+        configure_router(cell, self.top_layer);
+
+        io.layout.vdd.set_primary(vdd_tap);
+        io.layout.vss.set_primary(vss_tap);
+        io.layout.sb.merge(pmos_row[0].layout.io().g);
+        io.layout.rb.merge(pmos_row[2].layout.io().g);
+        io.layout.q.merge(pmos_row[0].layout.io().sd[1].clone());
+        io.layout.qb.merge(pmos_row[2].layout.io().sd[1].clone());
+
+        Ok(((), ()))
+    }
+}
+
+/// An Atoll [`LatchedComparator`](crate::latch::LatchedComparator).
+///
+/// The latch is placed to the right of the comparator, aligned to its top edge.
+/// Routes up to the higher of the top layers of the comparator and the latch.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "LatchedComparatorIo")]
+pub struct AtollLatchedComparator<T> {
+    pub comparator: T,
+    pub latch: AtollSrLatch,
+}
+
+impl<T: Tile<Sky130Pdk> + Block<Io = ClockedDiffComparatorIo> + TopLayer> ExportsNestedData
+    for AtollLatchedComparator<T>
+{
+    type NestedData = ();
+}
 
-        io.layout.vdd.set_primary(ntap.layout.io().vpb.primary);
-        io.layout.vss.set_primary(ptap.layout.io().vnb.primary);
+impl<T: Tile<Sky130Pdk> + Block<Io = ClockedDiffComparatorIo> + TopLayer> ExportsLayoutData
+    for AtollLatchedComparator<T>
+{
+    type LayoutData = ();
+}
 
-        let m1slice = cell.layer_stack.slice(0..2);
+impl<T: Tile<Sky130Pdk> + Block<Io = ClockedDiffComparatorIo> + TopLayer> Tile<Sky130Pdk>
+    for AtollLatchedComparator<T>
+{
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let (vdd, vss) = (io.schematic.vdd, io.schematic.vss);
 
-        let mut lcm_tracks = Vec::new();
-        lcm_tracks.push(
-            m1slice
-                .shrink_to_lcm_units(tail_pair[0].layout.io().g.primary.bbox().unwrap())
-                .unwrap()
-                .bot(),
+        let comparator = cell.generate_connected(
+            self.comparator.clone(),
+            Bundle::<ClockedDiffComparatorIo> {
+                input: io.schematic.input.clone(),
+                output: io.schematic.output.clone(),
+                clock: io.schematic.clock,
+                vdd,
+                vss,
+            },
         );
-        for io in [input_pair[0].layout.io(), inv_pmos_pair[0].layout.io()] {
-            let bot_track = m1slice
-                .expand_to_lcm_units(io.g.primary.bbox().unwrap())
-                .bot();
-            lcm_tracks.push(bot_track);
-            lcm_tracks.push(bot_track + 1);
-        }
-
-        for (i, port) in [
-            io.schematic.clock,
-            io.schematic.input.p,
-            io.schematic.input.n,
-            io.schematic.output.p,
-            io.schematic.output.n,
-        ]
-        .into_iter()
-        .enumerate()
-        {
-            cell.assign_grid_points(
-                port,
-                1,
-                Rect::from_spans(strongarm_lcm_hspan, Span::from_point(lcm_tracks[i])),
-            );
+        let mut latch = cell.generate_connected(
+            self.latch,
+            Bundle::<SrLatchIo> {
+                sb: io.schematic.output.n,
+                rb: io.schematic.output.p,
+                q: io.schematic.q,
+                qb: io.schematic.qb,
+                vdd,
+                vss,
+            },
+        );
+
+        latch.align_rect_mut(comparator.lcm_bounds(), AlignMode::Top, 0);
+        latch.align_rect_mut(comparator.lcm_bounds(), AlignMode::ToTheRight, 0);
+
+        let comparator = cell.draw(comparator)?;
+        let latch = cell.draw(latch)?;
+
+        configure_router(cell, self.top_layer());
+
+        let comparator_io = comparator.layout.io();
+        io.layout.input.p.set_primary(comparator_io.input.p.primary);
+        io.layout.input.n.set_primary(comparator_io.input.n.primary);
+        io.layout
+            .output
+            .p
+            .set_primary(comparator_io.output.p.primary);
+        io.layout
+            .output
+            .n
+            .set_primary(comparator_io.output.n.primary);
+        io.layout.clock.set_primary(comparator_io.clock.primary);
+        io.layout.vdd.set_primary(comparator_io.vdd.primary);
+        io.layout.vss.set_primary(comparator_io.vss.primary);
+        io.layout.q.merge(latch.layout.io().q);
+        io.layout.qb.merge(latch.layout.io().qb);
+
+        Ok(((), ()))
+    }
+}
+
+impl<T: TopLayer> TopLayer for AtollLatchedComparator<T> {
+    fn top_layer(&self) -> Metal {
+        self.comparator.top_layer().max(self.latch.top_layer)
+    }
+}
+
+/// The height of each tap row, in LCM units.
+const TAP_HEIGHT: i64 = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TapKind {
+    Ptap,
+    Ntap,
+}
+
+/// Returns the dimensions to pass to [`PtapTile::new`] or [`NtapTile::new`] for a tap
+/// spanning exactly `lcm_width` by `lcm_height` LCM units.
+///
+/// The drawn geometry of a tap extends beyond the region it is given, so the
+/// overhang of a tap of the requested size is measured and subtracted.
+fn tap_dims(
+    cell: &mut TileBuilder<'_, Sky130Pdk>,
+    kind: TapKind,
+    lcm_width: i64,
+    lcm_height: i64,
+) -> (i64, i64) {
+    let bbox = match kind {
+        TapKind::Ptap => cell
+            .layout
+            .generate(PtapTile::new(lcm_width, lcm_height))
+            .bbox(),
+        TapKind::Ntap => cell
+            .layout
+            .generate(NtapTile::new(lcm_width, lcm_height))
+            .bbox(),
+    };
+    let lcm_bounds = cell
+        .layer_stack
+        .slice(0..2)
+        .expand_to_lcm_units(bbox.unwrap());
+    (
+        2 * lcm_width - lcm_bounds.width(),
+        2 * lcm_height - lcm_bounds.height(),
+    )
+}
+
+/// A P-tap or N-tap tile.
+enum TapInstance {
+    Ptap(Instance<PtapTile>),
+    Ntap(Instance<NtapTile>),
+}
+
+impl TapInstance {
+    /// Generates a tap spanning exactly `lcm_width` by `lcm_height` LCM units.
+    fn generate(
+        cell: &mut TileBuilder<'_, Sky130Pdk>,
+        kind: TapKind,
+        lcm_width: i64,
+        lcm_height: i64,
+    ) -> Self {
+        let (w, h) = tap_dims(cell, kind, lcm_width, lcm_height);
+        match kind {
+            TapKind::Ptap => Self::Ptap(cell.generate_primitive(PtapTile::new(w, h))),
+            TapKind::Ntap => Self::Ntap(cell.generate_primitive(NtapTile::new(w, h))),
         }
+    }
 
-        let m1slice = cell.layer_stack.slice(0..2);
+    /// Generates a tap row spanning exactly `lcm_width` LCM units.
+    fn generate_row(cell: &mut TileBuilder<'_, Sky130Pdk>, kind: TapKind, lcm_width: i64) -> Self {
+        Self::generate(cell, kind, lcm_width, TAP_HEIGHT)
+    }
 
-        let io_rects = lcm_tracks
-            .into_iter()
-            .map(|track| {
-                m1slice
-                    .lcm_to_physical_rect(Rect::from_spans(
-                        strongarm_lcm_hspan,
-                        Span::from_point(track),
-                    ))
-                    .expand_dir(Dir::Vert, 130)
-                    .translate(Point::new(0, 130))
-            })
-            .collect::<Vec<_>>();
-
-        for (i, port) in [
-            &mut io.layout.clock,
-            &mut io.layout.input.p,
-            &mut io.layout.input.n,
-            &mut io.layout.output.p,
-            &mut io.layout.output.n,
-        ]
-        .into_iter()
-        .enumerate()
+    fn lcm_bounds(&self) -> Rect {
+        match self {
+            Self::Ptap(tap) => tap.lcm_bounds(),
+            Self::Ntap(tap) => tap.lcm_bounds(),
+        }
+    }
+
+    fn align_rect_mut(&mut self, rect: Rect, mode: AlignMode, offset: i64) {
+        match self {
+            Self::Ptap(tap) => tap.align_rect_mut(rect, mode, offset),
+            Self::Ntap(tap) => tap.align_rect_mut(rect, mode, offset),
+        }
+    }
+
+    /// Connects the tap to `node`.
+    fn connect(&self, cell: &mut TileBuilder<'_, Sky130Pdk>, node: Node) {
+        match self {
+            Self::Ptap(tap) => cell.connect(tap.io().vnb, node),
+            Self::Ntap(tap) => cell.connect(tap.io().vpb, node),
+        }
+    }
+
+    /// Draws the tap, returning its shape.
+    fn draw(self, cell: &mut TileBuilder<'_, Sky130Pdk>) -> Result<IoShape> {
+        Ok(match self {
+            Self::Ptap(tap) => cell.draw(tap)?.layout.io().vnb.primary,
+            Self::Ntap(tap) => cell.draw(tap)?.layout.io().vpb.primary,
+        })
+    }
+}
+
+/// The schematic node and layout port of a comparator port.
+type Port<'b> = (Node, &'b mut PortGeometryBuilder);
+
+/// The ports of a comparator corresponding to the ports of a [`ClockedDiffComparatorIo`].
+struct ComparatorPorts<'b> {
+    clock: Port<'b>,
+    input_p: Port<'b>,
+    input_n: Port<'b>,
+    output_p: Port<'b>,
+    output_n: Port<'b>,
+    vdd: Port<'b>,
+    vss: Port<'b>,
+}
+
+impl<'b> ComparatorPorts<'b> {
+    fn new(
+        schematic: &Bundle<ClockedDiffComparatorIo>,
+        layout: &'b mut Builder<ClockedDiffComparatorIo>,
+    ) -> Self {
+        Self {
+            clock: (schematic.clock, &mut layout.clock),
+            input_p: (schematic.input.p, &mut layout.input.p),
+            input_n: (schematic.input.n, &mut layout.input.n),
+            output_p: (schematic.output.p, &mut layout.output.p),
+            output_n: (schematic.output.n, &mut layout.output.n),
+            vdd: (schematic.vdd, &mut layout.vdd),
+            vss: (schematic.vss, &mut layout.vss),
+        }
+    }
+}
+
+/// Draws the pins of a comparator as specified by `pins`.
+///
+/// `extra` contains the ports of the comparator beyond those of a [`ClockedDiffComparatorIo`]
+/// and their placements. The LCM tracks of `placement` and its tap row shapes are used for
+/// pins with [`PinPlacement::Auto`]. Its power straps are added to the supply ports.
+fn draw_comparator_pins<'b>(
+    cell: &mut TileBuilder<'_, Sky130Pdk>,
+    placement: &ComparatorPlacement,
+    ports: ComparatorPorts<'b>,
+    pins: &ComparatorPins,
+    extra: impl IntoIterator<Item = (Port<'b>, PinPlacement)>,
+) -> Result<()> {
+    let ComparatorPorts {
+        clock,
+        input_p,
+        input_n,
+        output_p,
+        output_n,
+        vdd,
+        vss,
+    } = ports;
+    let lcm_bounds = placement.lcm_bounds;
+    let signals = [
+        (clock, pins.clock),
+        (input_p, pins.input_p),
+        (input_n, pins.input_n),
+        (output_p, pins.output_p),
+        (output_n, pins.output_n),
+    ]
+    .into_iter()
+    .chain(extra)
+    .zip(placement.lcm_tracks.iter().copied())
+    .collect::<Vec<_>>();
+
+    // The met1 stripes of the automatically placed signal pins, which edge pins must avoid.
+    let stripes = signals
+        .iter()
+        .filter(|((_, pin), _)| *pin == PinPlacement::Auto)
+        .map(|(_, track)| track_pin_rect(cell, lcm_bounds.hspan(), *track))
+        .collect::<Vec<_>>();
+
+    for ((node, port), pin, tap, straps) in [
+        (vdd, pins.vdd, placement.vdd.clone(), &placement.vdd_straps),
+        (vss, pins.vss, placement.vss.clone(), &placement.vss_straps),
+    ] {
+        match pin {
+            PinPlacement::Auto => port.set_primary(tap),
+            PinPlacement::Edge(pin) => draw_edge_pin(cell, lcm_bounds, &stripes, node, port, pin)?,
+        }
+        for strap in straps {
+            port.push(strap.clone());
+        }
+    }
+    for (((node, port), pin), track) in signals {
+        match pin {
+            PinPlacement::Auto => draw_track_pin(cell, lcm_bounds.hspan(), node, port, track)?,
+            PinPlacement::Edge(pin) => draw_edge_pin(cell, lcm_bounds, &stripes, node, port, pin)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the physical rectangle of a full-width met1 pin spanning `lcm_hspan`
+/// on the given LCM track.
+fn track_pin_rect(cell: &TileBuilder<'_, Sky130Pdk>, lcm_hspan: Span, track: i64) -> Rect {
+    cell.layer_stack
+        .slice(0..2)
+        .lcm_to_physical_rect(Rect::from_spans(lcm_hspan, Span::from_point(track)))
+        .expand_dir(Dir::Vert, 130)
+        .translate(Point::new(0, 130))
+}
+
+/// Draws a full-width met1 pin spanning `lcm_hspan` on the given LCM track.
+fn draw_track_pin(
+    cell: &mut TileBuilder<'_, Sky130Pdk>,
+    lcm_hspan: Span,
+    node: Node,
+    port: &mut PortGeometryBuilder,
+    track: i64,
+) -> Result<()> {
+    cell.assign_grid_points(
+        node,
+        1,
+        Rect::from_spans(lcm_hspan, Span::from_point(track)),
+    );
+
+    let io_rect = track_pin_rect(cell, lcm_hspan, track);
+    port.set_primary(draw_metal(cell, Metal::Met1, io_rect)?);
+
+    Ok(())
+}
+
+/// Returns whether `a` and `b` overlap with a nonzero area.
+fn overlaps(a: Rect, b: Rect) -> bool {
+    a.left() < b.right() && b.left() < a.right() && a.bot() < b.top() && b.bot() < a.top()
+}
+
+/// Draws a [`PowerStraps`] mesh over a comparator occupying `lcm_bounds`.
+///
+/// Returns the shapes of the VDD and VSS straps, or an error if `top_layer` is
+/// beneath met3 or `straps` has a nonpositive width or a pitch of less than twice
+/// its width.
+fn draw_power_straps(
+    cell: &mut TileBuilder<'_, Sky130Pdk>,
+    lcm_bounds: Rect,
+    top_layer: Metal,
+    straps: PowerStraps,
+    vdd: Node,
+    vss: Node,
+) -> Result<(Vec<IoShape>, Vec<IoShape>)> {
+    if top_layer < Metal::Met3 {
+        return Err(InvalidParams(format!(
+            "power straps require a top routing layer of at least met3, got {top_layer:?}"
+        ))
+        .into());
+    }
+    if straps.width <= 0 {
+        return Err(InvalidParams(format!(
+            "power strap width must be positive, got {}",
+            straps.width
+        ))
+        .into());
+    }
+    if straps.pitch < 2 * straps.width {
+        return Err(InvalidParams(format!(
+            "power strap pitch must be at least twice the strap width of {}, got {}",
+            straps.width, straps.pitch
+        ))
+        .into());
+    }
+
+    let (mut vdd_straps, mut vss_straps) = (Vec::new(), Vec::new());
+    for layer in [top_layer.below().unwrap(), top_layer] {
+        let (span, cross) = if layer.is_horizontal() {
+            (lcm_bounds.vspan(), lcm_bounds.hspan())
+        } else {
+            (lcm_bounds.hspan(), lcm_bounds.vspan())
+        };
+
+        for (i, start) in (span.start()..=span.stop() - straps.width)
+            .step_by(straps.pitch as usize)
+            .enumerate()
         {
-            cell.layout
-                .draw(Shape::new(cell.ctx().layers.met1, io_rects[i]))?;
-            port.set_primary(IoShape::with_layers(cell.ctx().layers.met1, io_rects[i]));
+            let strap = Span::new(start, start + straps.width);
+            let lcm_rect = if layer.is_horizontal() {
+                Rect::from_spans(cross, strap)
+            } else {
+                Rect::from_spans(strap, cross)
+            };
+            let (node, shapes) = if i % 2 == 0 {
+                (vdd, &mut vdd_straps)
+            } else {
+                (vss, &mut vss_straps)
+            };
+            cell.assign_grid_points(node, layer.index(), lcm_rect);
+
+            let rect = cell.layer_stack.slice(0..2).lcm_to_physical_rect(lcm_rect);
+            shapes.push(draw_metal(cell, layer, rect)?);
         }
+    }
 
-        Ok(((), ()))
+    Ok((vdd_straps, vss_straps))
+}
+
+/// Draws `rect` on the given metal layer and returns the drawn shape.
+fn draw_metal(cell: &mut TileBuilder<'_, Sky130Pdk>, metal: Metal, rect: Rect) -> Result<IoShape> {
+    Ok(match metal {
+        Metal::Met1 => {
+            let layer = cell.ctx().layers.met1;
+            cell.layout.draw(Shape::new(layer, rect))?;
+            IoShape::with_layers(layer, rect)
+        }
+        Metal::Met2 => {
+            let layer = cell.ctx().layers.met2;
+            cell.layout.draw(Shape::new(layer, rect))?;
+            IoShape::with_layers(layer, rect)
+        }
+        Metal::Met3 => {
+            let layer = cell.ctx().layers.met3;
+            cell.layout.draw(Shape::new(layer, rect))?;
+            IoShape::with_layers(layer, rect)
+        }
+        Metal::Met4 => {
+            let layer = cell.ctx().layers.met4;
+            cell.layout.draw(Shape::new(layer, rect))?;
+            IoShape::with_layers(layer, rect)
+        }
+        Metal::Met5 => {
+            let layer = cell.ctx().layers.met5;
+            cell.layout.draw(Shape::new(layer, rect))?;
+            IoShape::with_layers(layer, rect)
+        }
+    })
+}
+
+/// Draws a pin on an edge of a comparator occupying `lcm_bounds`.
+///
+/// Returns an error if the pin has an odd or nonpositive width, if its track lies
+/// outside the edge, or if a met1 pin overlaps any of the met1 `stripes` of the
+/// automatically placed pins.
+fn draw_edge_pin(
+    cell: &mut TileBuilder<'_, Sky130Pdk>,
+    lcm_bounds: Rect,
+    stripes: &[Rect],
+    node: Node,
+    port: &mut PortGeometryBuilder,
+    pin: EdgePin,
+) -> Result<()> {
+    if pin.width <= 0 || pin.width % 2 != 0 {
+        return Err(InvalidParams(format!(
+            "edge pin width must be positive and even, got {}",
+            pin.width
+        ))
+        .into());
     }
+    let edge_len = match pin.edge {
+        Side::Left | Side::Right => lcm_bounds.height(),
+        Side::Bot | Side::Top => lcm_bounds.width(),
+    };
+    if !(0..=edge_len).contains(&pin.track) {
+        return Err(InvalidParams(format!(
+            "edge pin track {} lies outside the {:?} edge, which spans tracks 0 to {edge_len}",
+            pin.track, pin.edge
+        ))
+        .into());
+    }
+
+    let (left, bot) = (lcm_bounds.left(), lcm_bounds.bot());
+    let (right, top) = (lcm_bounds.right(), lcm_bounds.top());
+    let (lcm_rect, dir) = match pin.edge {
+        Side::Left => (
+            Rect::from_spans(Span::new(left, left + 1), Span::from_point(bot + pin.track)),
+            Dir::Vert,
+        ),
+        Side::Right => (
+            Rect::from_spans(
+                Span::new(right - 1, right),
+                Span::from_point(bot + pin.track),
+            ),
+            Dir::Vert,
+        ),
+        Side::Bot => (
+            Rect::from_spans(Span::from_point(left + pin.track), Span::new(bot, bot + 1)),
+            Dir::Horiz,
+        ),
+        Side::Top => (
+            Rect::from_spans(Span::from_point(left + pin.track), Span::new(top - 1, top)),
+            Dir::Horiz,
+        ),
+    };
+
+    let io_rect = cell
+        .layer_stack
+        .slice(0..2)
+        .lcm_to_physical_rect(lcm_rect)
+        .expand_dir(dir, pin.width / 2);
+
+    if pin.layer == PinLayer::Met1 && stripes.iter().any(|stripe| overlaps(io_rect, *stripe)) {
+        return Err(InvalidParams(format!(
+            "met1 pin on track {} of the {:?} edge overlaps an automatically placed met1 pin",
+            pin.track, pin.edge
+        ))
+        .into());
+    }
+
+    cell.assign_grid_points(node, Metal::from(pin.layer).index(), lcm_rect);
+    port.set_primary(draw_metal(cell, pin.layer.into(), io_rect)?);
+
+    Ok(())
 }
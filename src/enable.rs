@@ -0,0 +1,165 @@
+//! Comparators with an enable input.
+
+use serde::{Deserialize, Serialize};
+use sky130pdk::mos::{MosParams, Nfet01v8, Pfet01v8};
+use sky130pdk::Sky130Pdk;
+use substrate::block::Block;
+use substrate::io::schematic::{Bundle, HardwareType};
+use substrate::io::{DiffPair, InOut, Input, Io, MosIoSchematic, Output, Signal};
+use substrate::schematic::{CellBuilder, ExportsNestedData, Schematic};
+
+use crate::{instantiate_mos, ClockedDiffComparatorIo, MosKind, StrongArmInstance};
+
+#[derive(Debug, Default, Clone, Io)]
+pub struct EnabledComparatorIo {
+    pub input: Input<DiffPair>,
+    pub output: Output<DiffPair>,
+    pub clock: Input<Signal>,
+    pub vdd: InOut<Signal>,
+    pub vss: InOut<Signal>,
+    /// The active-high enable input.
+    pub enable: Input<Signal>,
+}
+
+/// A [`StrongArmInstance`] that can be powered down.
+///
+/// A switch in series with the tail device cuts off the tail current while
+/// `enable` is low, and a pair of devices holds both outputs at their reset
+/// value: `vdd` for an NMOS input pair and `vss` for a PMOS input pair.
+/// With a PMOS input pair, an internal inverter generates the complement of
+/// `enable` to drive these devices.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "EnabledComparatorIo")]
+pub struct EnabledStrongArmInstance {
+    pub strongarm: StrongArmInstance,
+    /// The parameters of the tail switch, output forcing devices, and enable inverter.
+    pub switch: MosParams,
+}
+
+impl ExportsNestedData for EnabledStrongArmInstance {
+    type NestedData = ();
+}
+
+impl Schematic<Sky130Pdk> for EnabledStrongArmInstance {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let input_kind = self.strongarm.input_kind;
+        let reset_kind = input_kind.complement();
+        let (input_rail, reset_rail) = match input_kind {
+            MosKind::Nmos => (io.vss, io.vdd),
+            MosKind::Pmos => (io.vdd, io.vss),
+        };
+
+        let enable = match input_kind {
+            MosKind::Nmos => io.enable,
+            MosKind::Pmos => {
+                let enableb = cell.signal("enableb", Signal);
+                cell.instantiate_connected(
+                    Nfet01v8::new(self.switch),
+                    MosIoSchematic {
+                        d: enableb,
+                        g: io.enable,
+                        s: io.vss,
+                        b: io.vss,
+                    },
+                );
+                cell.instantiate_connected(
+                    Pfet01v8::new(self.switch),
+                    MosIoSchematic {
+                        d: enableb,
+                        g: io.enable,
+                        s: io.vdd,
+                        b: io.vdd,
+                    },
+                );
+                enableb
+            }
+        };
+
+        let tail_source = cell.signal("tail_source", Signal);
+        instantiate_mos(
+            cell,
+            input_kind,
+            self.switch,
+            MosIoSchematic {
+                d: tail_source,
+                g: enable,
+                s: input_rail,
+                b: input_rail,
+            },
+        );
+
+        self.strongarm.instantiate_devices(
+            &Bundle::<ClockedDiffComparatorIo> {
+                input: io.input.clone(),
+                output: io.output.clone(),
+                clock: io.clock,
+                vdd: io.vdd,
+                vss: io.vss,
+            },
+            Some(tail_source),
+            cell,
+        );
+
+        for output in [io.output.n, io.output.p] {
+            instantiate_mos(
+                cell,
+                reset_kind,
+                self.switch,
+                MosIoSchematic {
+                    d: output,
+                    g: enable,
+                    s: reset_rail,
+                    b: reset_rail,
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A comparator with its enable input tied to a fixed value.
+///
+/// Exposes a [`ClockedDiffComparatorIo`], so it can be used in any of
+/// the comparator testbenches.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "ClockedDiffComparatorIo")]
+pub struct FixedEnable<T> {
+    pub comparator: T,
+    /// Whether the comparator is enabled.
+    pub enabled: bool,
+}
+
+impl<T: Block<Io = EnabledComparatorIo> + Schematic<Sky130Pdk>> ExportsNestedData
+    for FixedEnable<T>
+{
+    type NestedData = ();
+}
+
+impl<T: Block<Io = EnabledComparatorIo> + Schematic<Sky130Pdk>> Schematic<Sky130Pdk>
+    for FixedEnable<T>
+{
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        cell.instantiate_connected(
+            self.comparator.clone(),
+            Bundle::<EnabledComparatorIo> {
+                input: io.input.clone(),
+                output: io.output.clone(),
+                clock: io.clock,
+                vdd: io.vdd,
+                vss: io.vss,
+                enable: if self.enabled { io.vdd } else { io.vss },
+            },
+        );
+
+        Ok(())
+    }
+}
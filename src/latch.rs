@@ -0,0 +1,209 @@
+//! Set-reset latch output stages.
+
+use serde::{Deserialize, Serialize};
+use sky130pdk::mos::{MosParams, Nfet01v8, Pfet01v8};
+use sky130pdk::Sky130Pdk;
+use substrate::block::Block;
+use substrate::io::schematic::{Bundle, HardwareType, Node};
+use substrate::io::{DiffPair, InOut, Input, Io, MosIoSchematic, Output, Signal};
+use substrate::schematic::{CellBuilder, ExportsNestedData, Schematic};
+
+use crate::ClockedDiffComparatorIo;
+
+#[derive(Debug, Default, Clone, Io)]
+pub struct SrLatchIo {
+    /// The active-low set input.
+    pub sb: Input<Signal>,
+    /// The active-low reset input.
+    pub rb: Input<Signal>,
+    pub q: Output<Signal>,
+    pub qb: Output<Signal>,
+    pub vdd: InOut<Signal>,
+    pub vss: InOut<Signal>,
+}
+
+/// A set-reset latch built from two cross-coupled NAND gates.
+///
+/// The latch holds its state while both `sb` and `rb` are high.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "SrLatchIo")]
+pub struct SrLatch {
+    pub nmos: MosParams,
+    pub pmos: MosParams,
+}
+
+impl ExportsNestedData for SrLatch {
+    type NestedData = ();
+}
+
+impl SrLatch {
+    /// Instantiates a NAND gate driving `y` from inputs `a` and `b`.
+    ///
+    /// `x` is the internal node between the series NMOS devices.
+    #[allow(clippy::too_many_arguments)]
+    fn nand(
+        &self,
+        cell: &mut CellBuilder<Sky130Pdk>,
+        a: Node,
+        b: Node,
+        y: Node,
+        x: Node,
+        vdd: Node,
+        vss: Node,
+    ) {
+        for g in [a, b] {
+            cell.instantiate_connected(
+                Pfet01v8::new(self.pmos),
+                MosIoSchematic {
+                    d: y,
+                    g,
+                    s: vdd,
+                    b: vdd,
+                },
+            );
+        }
+        cell.instantiate_connected(
+            Nfet01v8::new(self.nmos),
+            MosIoSchematic {
+                d: y,
+                g: a,
+                s: x,
+                b: vss,
+            },
+        );
+        cell.instantiate_connected(
+            Nfet01v8::new(self.nmos),
+            MosIoSchematic {
+                d: x,
+                g: b,
+                s: vss,
+                b: vss,
+            },
+        );
+    }
+}
+
+impl Schematic<Sky130Pdk> for SrLatch {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let q_x = cell.signal("q_x", Signal);
+        let qb_x = cell.signal("qb_x", Signal);
+
+        self.nand(cell, io.sb, io.qb, io.q, q_x, io.vdd, io.vss);
+        self.nand(cell, io.rb, io.q, io.qb, qb_x, io.vdd, io.vss);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Io)]
+pub struct LatchedComparatorIo {
+    pub input: Input<DiffPair>,
+    pub output: Output<DiffPair>,
+    pub clock: Input<Signal>,
+    pub vdd: InOut<Signal>,
+    pub vss: InOut<Signal>,
+    /// The latched decision, high if the last comparison was positive.
+    pub q: Output<Signal>,
+    /// The complement of `q`.
+    pub qb: Output<Signal>,
+}
+
+/// A comparator followed by an [`SrLatch`] that holds its decision.
+///
+/// The comparator outputs must reset high, as in the NMOS-input
+/// [`StrongArmInstance`](crate::StrongArmInstance), so that the latch holds
+/// its state during the reset phase. `output.n` drives the set input and
+/// `output.p` drives the reset input of the latch.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "LatchedComparatorIo")]
+pub struct LatchedComparator<T> {
+    pub comparator: T,
+    pub latch: SrLatch,
+}
+
+impl<T: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk>> ExportsNestedData
+    for LatchedComparator<T>
+{
+    type NestedData = ();
+}
+
+impl<T: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk>> Schematic<Sky130Pdk>
+    for LatchedComparator<T>
+{
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        cell.instantiate_connected(
+            self.comparator.clone(),
+            Bundle::<ClockedDiffComparatorIo> {
+                input: io.input.clone(),
+                output: io.output.clone(),
+                clock: io.clock,
+                vdd: io.vdd,
+                vss: io.vss,
+            },
+        );
+
+        cell.instantiate_connected(
+            self.latch,
+            Bundle::<SrLatchIo> {
+                sb: io.output.n,
+                rb: io.output.p,
+                q: io.q,
+                qb: io.qb,
+                vdd: io.vdd,
+                vss: io.vss,
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// A [`LatchedComparator`] whose outputs are the latch outputs.
+///
+/// `output.p` is driven by `q` and `output.n` by `qb`; the comparator outputs are
+/// internal. Exposes a [`ClockedDiffComparatorIo`], so the latched decision can be
+/// observed in any of the comparator testbenches.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "ClockedDiffComparatorIo")]
+pub struct LatchOutputs<T>(pub LatchedComparator<T>);
+
+impl<T: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk>> ExportsNestedData
+    for LatchOutputs<T>
+{
+    type NestedData = ();
+}
+
+impl<T: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk>> Schematic<Sky130Pdk>
+    for LatchOutputs<T>
+{
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let output = cell.signal("comparator_output", DiffPair::default());
+
+        cell.instantiate_connected(
+            self.0.clone(),
+            Bundle::<LatchedComparatorIo> {
+                input: io.input.clone(),
+                output,
+                clock: io.clock,
+                vdd: io.vdd,
+                vss: io.vss,
+                q: io.output.p,
+                qb: io.output.n,
+            },
+        );
+
+        Ok(())
+    }
+}
@@ -1,16 +1,22 @@
+use ngspice::Ngspice;
 use serde::{Deserialize, Serialize};
 use sky130pdk::mos::MosParams;
 use sky130pdk::mos::{Nfet01v8, Pfet01v8};
 use sky130pdk::Sky130Pdk;
 use spectre::Spectre;
+use std::fmt;
+use std::sync::Arc;
 use substrate::block::Block;
 use substrate::context::{Context, PdkContext};
-use substrate::io::schematic::HardwareType;
+use substrate::io::schematic::{Bundle, HardwareType, Node};
 use substrate::io::{DiffPair, InOut, Input, Io, MosIoSchematic, Output, Signal};
 use substrate::schematic::{CellBuilder, ExportsNestedData, Schematic};
 
 pub mod atoll;
+pub mod enable;
+pub mod latch;
 pub mod tb;
+pub mod trim;
 
 #[derive(Debug, Default, Clone, Io)]
 pub struct ClockedDiffComparatorIo {
@@ -20,6 +26,68 @@ pub struct ClockedDiffComparatorIo {
     pub vdd: InOut<Signal>,
     pub vss: InOut<Signal>,
 }
+
+/// An error indicating that a block or testbench was given invalid parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidParams(pub String);
+
+impl fmt::Display for InvalidParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid parameters: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidParams {}
+
+impl From<InvalidParams> for substrate::error::Error {
+    fn from(value: InvalidParams) -> Self {
+        Self::Boxed(Arc::new(value))
+    }
+}
+
+/// The type of a MOS device.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum MosKind {
+    #[default]
+    Nmos,
+    Pmos,
+}
+
+impl MosKind {
+    /// Returns the complementary device type.
+    pub fn complement(&self) -> Self {
+        match self {
+            MosKind::Nmos => MosKind::Pmos,
+            MosKind::Pmos => MosKind::Nmos,
+        }
+    }
+}
+
+/// Instantiates a Sky130 1.8V transistor of type `kind`.
+pub(crate) fn instantiate_mos(
+    cell: &mut CellBuilder<Sky130Pdk>,
+    kind: MosKind,
+    params: MosParams,
+    io: MosIoSchematic,
+) {
+    match kind {
+        MosKind::Nmos => {
+            cell.instantiate_connected(Nfet01v8::new(params), io);
+        }
+        MosKind::Pmos => {
+            cell.instantiate_connected(Pfet01v8::new(params), io);
+        }
+    }
+}
+
+/// A StrongARM comparator.
+///
+/// With an NMOS input pair, the tail connects to `vss` and the precharge devices
+/// pull the internal nodes up to `vdd` while the clock is low.
+/// With a PMOS input pair, the circuit is mirrored: the tail connects to `vdd`,
+/// the precharge devices discharge the internal nodes to `vss`, and an internal
+/// inverter sized by `precharge` drives the tail and precharge gates, so that
+/// both variants evaluate while the clock is high.
 #[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
 #[substrate(io = "ClockedDiffComparatorIo")]
 pub struct StrongArmInstance {
@@ -28,6 +96,8 @@ pub struct StrongArmInstance {
     pub inv_nmos: MosParams,
     pub inv_pmos: MosParams,
     pub precharge: MosParams,
+    /// The type of the input pair and tail devices.
+    pub input_kind: MosKind,
 }
 
 impl ExportsNestedData for StrongArmInstance {
@@ -40,97 +110,255 @@ impl Schematic<Sky130Pdk> for StrongArmInstance {
         io: &<<Self as Block>::Io as HardwareType>::Bundle,
         cell: &mut CellBuilder<Sky130Pdk>,
     ) -> substrate::error::Result<Self::NestedData> {
+        self.instantiate_devices(io, None, cell);
+        Ok(())
+    }
+}
+
+/// The internal nodes of a [`StrongArmInstance`].
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct StrongArmInternalNodes {
+    pub(crate) tail: Node,
+    pub(crate) intn: Node,
+    pub(crate) intp: Node,
+}
+
+impl StrongArmInstance {
+    /// Instantiates the devices of the comparator in `cell`, connected to `io`.
+    ///
+    /// The source of the tail device connects to `tail_source` if provided,
+    /// or directly to the supply rail otherwise.
+    ///
+    /// Returns the internal nodes of the comparator, so that blocks
+    /// built around the comparator can attach circuitry to them.
+    pub(crate) fn instantiate_devices(
+        &self,
+        io: &Bundle<ClockedDiffComparatorIo>,
+        tail_source: Option<Node>,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> StrongArmInternalNodes {
         let tail = cell.signal("tail", Signal);
         let intn = cell.signal("intn", Signal);
         let intp = cell.signal("intp", Signal);
 
-        cell.instantiate_connected(
-            Nfet01v8::new(self.tail),
+        let input_kind = self.input_kind;
+        let reset_kind = input_kind.complement();
+        let (input_rail, reset_rail, inv_input, inv_reset) = match input_kind {
+            MosKind::Nmos => (io.vss, io.vdd, self.inv_nmos, self.inv_pmos),
+            MosKind::Pmos => (io.vdd, io.vss, self.inv_pmos, self.inv_nmos),
+        };
+
+        let clock = match input_kind {
+            MosKind::Nmos => io.clock,
+            MosKind::Pmos => {
+                let clockb = cell.signal("clockb", Signal);
+                cell.instantiate_connected(
+                    Nfet01v8::new(self.precharge),
+                    MosIoSchematic {
+                        d: clockb,
+                        g: io.clock,
+                        s: io.vss,
+                        b: io.vss,
+                    },
+                );
+                cell.instantiate_connected(
+                    Pfet01v8::new(self.precharge),
+                    MosIoSchematic {
+                        d: clockb,
+                        g: io.clock,
+                        s: io.vdd,
+                        b: io.vdd,
+                    },
+                );
+                clockb
+            }
+        };
+
+        instantiate_mos(
+            cell,
+            input_kind,
+            self.tail,
             MosIoSchematic {
                 d: tail,
-                g: io.clock,
-                s: io.vss,
-                b: io.vss,
+                g: clock,
+                s: tail_source.unwrap_or(input_rail),
+                b: input_rail,
             },
         );
 
-        cell.instantiate_connected(
-            Nfet01v8::new(self.input_pair),
+        instantiate_mos(
+            cell,
+            input_kind,
+            self.input_pair,
             MosIoSchematic {
                 d: intn,
                 g: io.input.p,
                 s: tail,
-                b: io.vss,
+                b: input_rail,
             },
         );
-        cell.instantiate_connected(
-            Nfet01v8::new(self.input_pair),
+        instantiate_mos(
+            cell,
+            input_kind,
+            self.input_pair,
             MosIoSchematic {
                 d: intp,
                 g: io.input.n,
                 s: tail,
-                b: io.vss,
+                b: input_rail,
             },
         );
 
-        cell.instantiate_connected(
-            Nfet01v8::new(self.inv_nmos),
+        instantiate_mos(
+            cell,
+            input_kind,
+            inv_input,
             MosIoSchematic {
                 d: io.output.n,
                 g: io.output.p,
                 s: intn,
-                b: io.vss,
+                b: input_rail,
             },
         );
-        cell.instantiate_connected(
-            Nfet01v8::new(self.inv_nmos),
+        instantiate_mos(
+            cell,
+            input_kind,
+            inv_input,
             MosIoSchematic {
                 d: io.output.p,
                 g: io.output.n,
                 s: intp,
-                b: io.vss,
+                b: input_rail,
             },
         );
 
-        cell.instantiate_connected(
-            Pfet01v8::new(self.inv_pmos),
+        instantiate_mos(
+            cell,
+            reset_kind,
+            inv_reset,
             MosIoSchematic {
                 d: io.output.n,
                 g: io.output.p,
-                s: io.vdd,
-                b: io.vdd,
+                s: reset_rail,
+                b: reset_rail,
             },
         );
-        cell.instantiate_connected(
-            Pfet01v8::new(self.inv_pmos),
+        instantiate_mos(
+            cell,
+            reset_kind,
+            inv_reset,
             MosIoSchematic {
                 d: io.output.p,
                 g: io.output.n,
-                s: io.vdd,
-                b: io.vdd,
+                s: reset_rail,
+                b: reset_rail,
             },
         );
 
+        for node in [io.output.n, io.output.p, intn, intp] {
+            instantiate_mos(
+                cell,
+                reset_kind,
+                self.precharge,
+                MosIoSchematic {
+                    d: node,
+                    g: clock,
+                    s: reset_rail,
+                    b: reset_rail,
+                },
+            );
+        }
+
+        StrongArmInternalNodes { tail, intn, intp }
+    }
+}
+
+/// A double-tail dynamic comparator.
+///
+/// A clocked preamplifier drives the `intn`/`intp` nodes, which in turn release a
+/// cross-coupled latch with its own PMOS tail. Separating the input stage from
+/// the latch reduces the number of stacked devices, allowing lower supply voltages
+/// than [`StrongArmInstance`].
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "ClockedDiffComparatorIo")]
+pub struct DoubleTailInstance {
+    pub preamp_tail: MosParams,
+    pub input_pair: MosParams,
+    pub preamp_reset: MosParams,
+    pub latch_tail: MosParams,
+    pub latch_nmos: MosParams,
+    pub latch_pmos: MosParams,
+    pub output_reset: MosParams,
+    pub clock_inv_nmos: MosParams,
+    pub clock_inv_pmos: MosParams,
+}
+
+impl ExportsNestedData for DoubleTailInstance {
+    type NestedData = ();
+}
+
+impl Schematic<Sky130Pdk> for DoubleTailInstance {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let clockb = cell.signal("clockb", Signal);
+        let preamp_tail = cell.signal("preamp_tail", Signal);
+        let latch_tail = cell.signal("latch_tail", Signal);
+        let intn = cell.signal("intn", Signal);
+        let intp = cell.signal("intp", Signal);
+
         cell.instantiate_connected(
-            Pfet01v8::new(self.precharge),
+            Nfet01v8::new(self.clock_inv_nmos),
             MosIoSchematic {
-                d: io.output.n,
+                d: clockb,
                 g: io.clock,
-                s: io.vdd,
-                b: io.vdd,
+                s: io.vss,
+                b: io.vss,
             },
         );
         cell.instantiate_connected(
-            Pfet01v8::new(self.precharge),
+            Pfet01v8::new(self.clock_inv_pmos),
             MosIoSchematic {
-                d: io.output.p,
+                d: clockb,
                 g: io.clock,
                 s: io.vdd,
                 b: io.vdd,
             },
         );
+
+        cell.instantiate_connected(
+            Nfet01v8::new(self.preamp_tail),
+            MosIoSchematic {
+                d: preamp_tail,
+                g: io.clock,
+                s: io.vss,
+                b: io.vss,
+            },
+        );
+
+        cell.instantiate_connected(
+            Nfet01v8::new(self.input_pair),
+            MosIoSchematic {
+                d: intn,
+                g: io.input.p,
+                s: preamp_tail,
+                b: io.vss,
+            },
+        );
+        cell.instantiate_connected(
+            Nfet01v8::new(self.input_pair),
+            MosIoSchematic {
+                d: intp,
+                g: io.input.n,
+                s: preamp_tail,
+                b: io.vss,
+            },
+        );
+
         cell.instantiate_connected(
-            Pfet01v8::new(self.precharge),
+            Pfet01v8::new(self.preamp_reset),
             MosIoSchematic {
                 d: intn,
                 g: io.clock,
@@ -139,7 +367,7 @@ impl Schematic<Sky130Pdk> for StrongArmInstance {
             },
         );
         cell.instantiate_connected(
-            Pfet01v8::new(self.precharge),
+            Pfet01v8::new(self.preamp_reset),
             MosIoSchematic {
                 d: intp,
                 g: io.clock,
@@ -148,6 +376,74 @@ impl Schematic<Sky130Pdk> for StrongArmInstance {
             },
         );
 
+        cell.instantiate_connected(
+            Pfet01v8::new(self.latch_tail),
+            MosIoSchematic {
+                d: latch_tail,
+                g: clockb,
+                s: io.vdd,
+                b: io.vdd,
+            },
+        );
+
+        cell.instantiate_connected(
+            Pfet01v8::new(self.latch_pmos),
+            MosIoSchematic {
+                d: io.output.n,
+                g: io.output.p,
+                s: latch_tail,
+                b: io.vdd,
+            },
+        );
+        cell.instantiate_connected(
+            Pfet01v8::new(self.latch_pmos),
+            MosIoSchematic {
+                d: io.output.p,
+                g: io.output.n,
+                s: latch_tail,
+                b: io.vdd,
+            },
+        );
+
+        cell.instantiate_connected(
+            Nfet01v8::new(self.latch_nmos),
+            MosIoSchematic {
+                d: io.output.n,
+                g: io.output.p,
+                s: io.vss,
+                b: io.vss,
+            },
+        );
+        cell.instantiate_connected(
+            Nfet01v8::new(self.latch_nmos),
+            MosIoSchematic {
+                d: io.output.p,
+                g: io.output.n,
+                s: io.vss,
+                b: io.vss,
+            },
+        );
+
+        // `intn` falls faster when `input.p` is higher, releasing `output.p` first.
+        cell.instantiate_connected(
+            Nfet01v8::new(self.output_reset),
+            MosIoSchematic {
+                d: io.output.p,
+                g: intn,
+                s: io.vss,
+                b: io.vss,
+            },
+        );
+        cell.instantiate_connected(
+            Nfet01v8::new(self.output_reset),
+            MosIoSchematic {
+                d: io.output.n,
+                g: intp,
+                s: io.vss,
+                b: io.vss,
+            },
+        );
+
         Ok(())
     }
 }
@@ -162,26 +458,61 @@ pub fn sky130_ctx() -> PdkContext<Sky130Pdk> {
         .with_pdk()
 }
 
+pub fn sky130_open_ctx() -> PdkContext<Sky130Pdk> {
+    let pdk_root = std::env::var("SKY130_OPEN_PDK_ROOT")
+        .expect("the SKY130_OPEN_PDK_ROOT environment variable must be set");
+    Context::builder()
+        .install(Ngspice::default())
+        .install(Sky130Pdk::open(pdk_root))
+        .build()
+        .with_pdk()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::atoll::AtollStrongArmInstance;
-    use crate::tb::{ComparatorDecision, StrongArmTranTb};
+    use crate::atoll::{
+        AtollAuxTrimStrongArmInstance, AtollDoubleTailInstance, AtollEnabledStrongArmInstance,
+        AtollLatchedComparator, AtollSrLatch, AtollStrongArmInstance, BoundaryTaps, ComparatorPins,
+        EdgePin, Metal, PairPlacement, PinLayer, PinPlacement, PowerStraps,
+    };
+    use crate::enable::{EnabledStrongArmInstance, FixedEnable};
+    use crate::latch::{LatchOutputs, LatchedComparator, SrLatch};
+    use crate::tb::calibration::{IdealTrimDac, StrongArmCalibrationTb};
+    use crate::tb::energy::StrongArmEnergyTb;
+    use crate::tb::leakage::StrongArmLeakageTb;
+    use crate::tb::montecarlo::{MismatchModels, OffsetDistribution, StrongArmMonteCarloOffsetTb};
+    use crate::tb::multicycle::StrongArmMultiCycleTb;
+    use crate::tb::noise::{inverse_normal_cdf, StrongArmNoiseTb};
+    use crate::tb::offset::{OffsetSearch, StrongArmOffsetTb};
+    use crate::tb::pvt::{StrongArmPvtSweep, SKY130_CORNERS};
+    use crate::tb::{
+        classify, crossing, integrate, ComparatorDecision, ComparatorOutcome, DecisionThreshold,
+        DecisionTolerance, DelayMeasurement, MeasurementError, StrongArmDelayTb, StrongArmStimulus,
+        StrongArmTranTb,
+    };
+    use crate::trim::{
+        AuxTrimStrongArmInstance, CapTrimDac, CapTrimStrongArmInstance, FixedAuxTrim, FixedCapTrim,
+        Rail,
+    };
     use ::atoll::TileWrapper;
     use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
+    use sky130pdk::atoll::MosLength;
     use sky130pdk::corner::Sky130Corner;
     use sky130pdk::Sky130CommercialSchema;
     use spice::netlist::NetlistOptions;
     use spice::Spice;
+    use std::collections::HashMap;
     use std::path::PathBuf;
+    use substrate::geometry::side::Side;
+    use substrate::layout::Layout;
     use substrate::pdk::corner::Pvt;
     use substrate::schematic::netlist::ConvertibleNetlister;
+    use substrate::simulation::{Simulator, Testbench};
 
-    #[test]
-    fn sim_strongarm() {
-        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm");
-        let dut = StrongArmInstance {
+    fn strongarm_dut() -> StrongArmInstance {
+        StrongArmInstance {
             tail: MosParams {
                 w: 5_000,
                 l: 150,
@@ -207,25 +538,133 @@ mod tests {
                 l: 150,
                 nf: 1,
             },
-        };
-        let pvt = Pvt {
-            corner: Sky130Corner::Tt,
-            voltage: dec!(1.8),
-            temp: dec!(25.0),
-        };
-        let ctx = sky130_ctx();
+            input_kind: MosKind::Nmos,
+        }
+    }
+
+    fn pmos_strongarm_dut() -> StrongArmInstance {
+        StrongArmInstance {
+            input_pair: MosParams {
+                w: 16_000,
+                l: 150,
+                nf: 1,
+            },
+            tail: MosParams {
+                w: 10_000,
+                l: 150,
+                nf: 1,
+            },
+            input_kind: MosKind::Pmos,
+            ..strongarm_dut()
+        }
+    }
+
+    fn atoll_strongarm_dut() -> TileWrapper<AtollStrongArmInstance> {
+        TileWrapper::new(AtollStrongArmInstance::default())
+    }
+
+    fn atoll_folded_strongarm_dut() -> TileWrapper<AtollStrongArmInstance> {
+        TileWrapper::new(AtollStrongArmInstance {
+            input_pair_w: 1_000,
+            input_pair_nf: 8,
+            ..Default::default()
+        })
+    }
+
+    fn atoll_common_centroid_strongarm_dut() -> TileWrapper<AtollStrongArmInstance> {
+        TileWrapper::new(AtollStrongArmInstance {
+            input_pair_w: 1_000,
+            input_pair_nf: 8,
+            pair_placement: PairPlacement::CommonCentroid,
+            ..Default::default()
+        })
+    }
+
+    fn atoll_pmos_strongarm_dut() -> TileWrapper<AtollStrongArmInstance> {
+        TileWrapper::new(AtollStrongArmInstance {
+            half_tail_w: 2_500,
+            input_pair_w: 8_000,
+            input_kind: MosKind::Pmos,
+            ..Default::default()
+        })
+    }
+
+    fn double_tail_dut() -> DoubleTailInstance {
+        DoubleTailInstance {
+            preamp_tail: MosParams {
+                w: 5_000,
+                l: 150,
+                nf: 1,
+            },
+            input_pair: MosParams {
+                w: 8_000,
+                l: 150,
+                nf: 1,
+            },
+            preamp_reset: MosParams {
+                w: 2_000,
+                l: 150,
+                nf: 1,
+            },
+            latch_tail: MosParams {
+                w: 4_000,
+                l: 150,
+                nf: 1,
+            },
+            latch_nmos: MosParams {
+                w: 2_000,
+                l: 150,
+                nf: 1,
+            },
+            latch_pmos: MosParams {
+                w: 4_000,
+                l: 150,
+                nf: 1,
+            },
+            output_reset: MosParams {
+                w: 2_000,
+                l: 150,
+                nf: 1,
+            },
+            clock_inv_nmos: MosParams {
+                w: 1_000,
+                l: 150,
+                nf: 1,
+            },
+            clock_inv_pmos: MosParams {
+                w: 2_000,
+                l: 150,
+                nf: 1,
+            },
+        }
+    }
 
+    fn atoll_double_tail_dut() -> TileWrapper<AtollDoubleTailInstance> {
+        TileWrapper::new(AtollDoubleTailInstance::default())
+    }
+
+    /// The differential inputs `vinp - vinn` applied by [`check_decisions`].
+    const OVERDRIVES: [Decimal; 8] = [
+        dec!(-1.8),
+        dec!(-0.5),
+        dec!(-0.1),
+        dec!(-0.05),
+        dec!(0.05),
+        dec!(0.1),
+        dec!(0.5),
+        dec!(1.8),
+    ];
+
+    /// Checks that `dut` makes the correct decision over a grid of input common modes
+    /// and overdrives using simulator `S`.
+    fn check_decisions<S, T>(ctx: &PdkContext<Sky130Pdk>, dut: T, work_dir: &str)
+    where
+        S: Simulator,
+        T: Copy,
+        StrongArmTranTb<T>: Testbench<S, Output = ComparatorOutcome>,
+    {
         for i in 3..=10 {
-            for j in [
-                dec!(-1.8),
-                dec!(-0.5),
-                dec!(-0.1),
-                dec!(-0.05),
-                dec!(0.05),
-                dec!(0.1),
-                dec!(0.5),
-                dec!(1.8),
-            ] {
+            for j in OVERDRIVES {
                 let vinn = dec!(0.18) * Decimal::from(i);
                 let vinp = vinn + j;
 
@@ -233,15 +672,144 @@ mod tests {
                     continue;
                 }
 
-                let tb = StrongArmTranTb {
-                    dut,
-                    vinp,
+                check_decision::<S, T>(ctx, dut, vinp, vinn, work_dir);
+            }
+        }
+    }
+
+    /// Checks that `dut` makes the correct decision using simulator `S`
+    /// over a grid of input common modes that extends down to `vss`,
+    /// skipping input voltages outside of `[vmin, vmax]`.
+    fn check_decisions_in_range<S, T>(
+        ctx: &PdkContext<Sky130Pdk>,
+        dut: T,
+        vmin: Decimal,
+        vmax: Decimal,
+        work_dir: &str,
+    ) where
+        S: Simulator,
+        T: Copy,
+        StrongArmTranTb<T>: Testbench<S, Output = ComparatorOutcome>,
+    {
+        for i in 0..=10 {
+            for j in OVERDRIVES {
+                let vinn = dec!(0.18) * Decimal::from(i);
+                let vinp = vinn + j;
+
+                if vinn < vmin || vinp < vmin || vinp > vmax {
+                    continue;
+                }
+
+                check_decision::<S, T>(ctx, dut, vinp, vinn, work_dir);
+            }
+        }
+    }
+
+    /// Checks that `dut` resolves to the sign of `vinp - vinn` using simulator `S`.
+    fn check_decision<S, T>(
+        ctx: &PdkContext<Sky130Pdk>,
+        dut: T,
+        vinp: Decimal,
+        vinn: Decimal,
+        work_dir: &str,
+    ) where
+        S: Simulator,
+        StrongArmTranTb<T>: Testbench<S, Output = ComparatorOutcome>,
+    {
+        let tb = StrongArmTranTb {
+            dut,
+            vinp,
+            vinn,
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::default(),
+            tolerance: DecisionTolerance::default(),
+        };
+        let outcome = ctx
+            .simulate::<S, _>(tb, work_dir)
+            .expect("failed to run simulation");
+        assert_eq!(
+            outcome,
+            ComparatorOutcome::Resolved(if vinp > vinn {
+                ComparatorDecision::Pos
+            } else {
+                ComparatorDecision::Neg
+            }),
+            "comparator produced incorrect decision"
+        );
+    }
+
+    #[test]
+    fn sim_strongarm() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm");
+        let dut = StrongArmInstance {
+            tail: MosParams {
+                w: 5_000,
+                l: 150,
+                nf: 1,
+            },
+            input_pair: MosParams {
+                w: 8_000,
+                l: 150,
+                nf: 1,
+            },
+            inv_nmos: MosParams {
+                w: 4_000,
+                l: 150,
+                nf: 1,
+            },
+            inv_pmos: MosParams {
+                w: 2_000,
+                l: 150,
+                nf: 1,
+            },
+            precharge: MosParams {
+                w: 2_000,
+                l: 150,
+                nf: 1,
+            },
+            input_kind: MosKind::Nmos,
+        };
+        let pvt = Pvt {
+            corner: Sky130Corner::Tt,
+            voltage: dec!(1.8),
+            temp: dec!(25.0),
+        };
+        let ctx = sky130_ctx();
+
+        for i in 3..=10 {
+            for j in [
+                dec!(-1.8),
+                dec!(-0.5),
+                dec!(-0.1),
+                dec!(-0.05),
+                dec!(0.05),
+                dec!(0.1),
+                dec!(0.5),
+                dec!(1.8),
+            ] {
+                let vinn = dec!(0.18) * Decimal::from(i);
+                let vinp = vinn + j;
+
+                if vinp < dec!(0.5) || vinp > dec!(1.8) {
+                    continue;
+                }
+
+                let tb = StrongArmTranTb {
+                    dut,
+                    vinp,
                     vinn,
                     pvt,
+                    stimulus: StrongArmStimulus::default(),
+                    tolerance: DecisionTolerance::default(),
                 };
                 let decision = ctx
-                    .simulate(tb, work_dir)
+                    .simulate::<Spectre, _>(tb, work_dir)
                     .expect("failed to run simulation")
+                    .decision()
                     .expect("comparator output did not rail");
                 assert_eq!(
                     decision,
@@ -256,6 +824,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sim_strongarm_ngspice() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm_ngspice");
+        let ctx = sky130_open_ctx();
+        check_decisions::<Ngspice, _>(&ctx, strongarm_dut(), work_dir);
+    }
+
     #[test]
     fn sim_atoll_strongarm() {
         let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_atoll_strongarm");
@@ -265,6 +840,7 @@ mod tests {
             inv_nmos_w: 2_000,
             inv_pmos_w: 1_000,
             precharge_w: 1_000,
+            ..Default::default()
         });
         let pvt = Pvt {
             corner: Sky130Corner::Tt,
@@ -296,10 +872,13 @@ mod tests {
                     vinp,
                     vinn,
                     pvt,
+                    stimulus: StrongArmStimulus::default(),
+                    tolerance: DecisionTolerance::default(),
                 };
                 let decision = ctx
-                    .simulate(tb, work_dir)
+                    .simulate::<Spectre, _>(tb, work_dir)
                     .expect("failed to run simulation")
+                    .decision()
                     .expect("comparator output did not rail");
                 assert_eq!(
                     decision,
@@ -315,25 +894,975 @@ mod tests {
     }
 
     #[test]
-    fn layout_strongarm() {
-        let work_dir = PathBuf::from(concat!(
+    fn sim_atoll_strongarm_ngspice() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_atoll_strongarm_ngspice"
+        );
+        let ctx = sky130_open_ctx();
+        check_decisions::<Ngspice, _>(&ctx, atoll_strongarm_dut(), work_dir);
+    }
+
+    #[test]
+    fn sim_atoll_long_input_strongarm() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_atoll_long_input_strongarm"
+        );
+        let ctx = sky130_ctx();
+        let dut = TileWrapper::new(AtollStrongArmInstance {
+            input_pair_l: MosLength::L500,
+            ..Default::default()
+        });
+        check_decisions::<Spectre, _>(&ctx, dut, work_dir);
+    }
+
+    #[test]
+    fn sim_atoll_folded_strongarm() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_atoll_folded_strongarm"
+        );
+        let ctx = sky130_ctx();
+        check_decisions::<Spectre, _>(&ctx, atoll_folded_strongarm_dut(), work_dir);
+    }
+
+    #[test]
+    fn sim_atoll_common_centroid_strongarm() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_atoll_common_centroid_strongarm"
+        );
+        let ctx = sky130_ctx();
+        check_decisions::<Spectre, _>(&ctx, atoll_common_centroid_strongarm_dut(), work_dir);
+    }
+
+    #[test]
+    fn sim_pmos_strongarm() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_pmos_strongarm");
+        let ctx = sky130_ctx();
+        check_decisions_in_range::<Spectre, _>(
+            &ctx,
+            pmos_strongarm_dut(),
+            dec!(0),
+            dec!(1.3),
+            work_dir,
+        );
+    }
+
+    #[test]
+    fn sim_atoll_pmos_strongarm() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_atoll_pmos_strongarm"
+        );
+        let ctx = sky130_ctx();
+        check_decisions_in_range::<Spectre, _>(
+            &ctx,
+            atoll_pmos_strongarm_dut(),
+            dec!(0),
+            dec!(1.3),
+            work_dir,
+        );
+    }
+
+    #[test]
+    fn sim_double_tail() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_double_tail");
+        let ctx = sky130_ctx();
+        check_decisions::<Spectre, _>(&ctx, double_tail_dut(), work_dir);
+    }
+
+    #[test]
+    fn sim_atoll_double_tail() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_atoll_double_tail");
+        let ctx = sky130_ctx();
+        check_decisions::<Spectre, _>(&ctx, atoll_double_tail_dut(), work_dir);
+    }
+
+    #[test]
+    fn sim_strongarm_delay() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm_delay");
+        let ctx = sky130_ctx();
+        let pvt = Pvt {
+            corner: Sky130Corner::Tt,
+            voltage: dec!(1.8),
+            temp: dec!(25.0),
+        };
+
+        let delays = [dec!(0.001), dec!(0.01), dec!(0.1)]
+            .into_iter()
+            .map(|overdrive| {
+                let tb = StrongArmDelayTb {
+                    tb: StrongArmTranTb {
+                        dut: strongarm_dut(),
+                        vinp: dec!(0.9) + overdrive / dec!(2),
+                        vinn: dec!(0.9) - overdrive / dec!(2),
+                        pvt,
+                        stimulus: StrongArmStimulus::default(),
+                        tolerance: DecisionTolerance::default(),
+                    },
+                    threshold: DecisionThreshold::HalfSupply,
+                };
+                ctx.simulate::<Spectre, _>(tb, work_dir)
+                    .expect("failed to run simulation")
+                    .delay
+                    .expect("comparator output did not resolve")
+            })
+            .collect::<Vec<_>>();
+
+        assert!(delays.iter().all(|&delay| delay > 0.0 && delay < 20e-9));
+        assert!(
+            delays.windows(2).all(|w| w[0] > w[1]),
+            "delay should decrease with increasing overdrive"
+        );
+    }
+
+    /// Measures the delay of `dut` with the given `overdrive` around `vcm`
+    /// at the typical corner.
+    fn measure_delay<T>(
+        ctx: &PdkContext<Sky130Pdk>,
+        dut: T,
+        vcm: Decimal,
+        overdrive: Decimal,
+        work_dir: &str,
+    ) -> DelayMeasurement
+    where
+        StrongArmDelayTb<T>: Testbench<Spectre, Output = DelayMeasurement>,
+    {
+        let tb = StrongArmDelayTb {
+            tb: StrongArmTranTb {
+                dut,
+                vinp: vcm + overdrive / dec!(2),
+                vinn: vcm - overdrive / dec!(2),
+                pvt: Pvt {
+                    corner: Sky130Corner::Tt,
+                    voltage: dec!(1.8),
+                    temp: dec!(25.0),
+                },
+                stimulus: StrongArmStimulus::default(),
+                tolerance: DecisionTolerance::default(),
+            },
+            threshold: DecisionThreshold::HalfSupply,
+        };
+        ctx.simulate::<Spectre, _>(tb, work_dir)
+            .expect("failed to run simulation")
+    }
+
+    #[test]
+    fn sim_pmos_strongarm_delay() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_pmos_strongarm_delay"
+        );
+        let ctx = sky130_ctx();
+
+        // The outputs of the PMOS-input variant reset low.
+        let measurement =
+            measure_delay(&ctx, pmos_strongarm_dut(), dec!(0.6), dec!(0.01), work_dir);
+
+        assert_eq!(
+            measurement.outcome,
+            ComparatorOutcome::Resolved(ComparatorDecision::Pos)
+        );
+        let delay = measurement
+            .delay
+            .expect("comparator output did not resolve");
+        assert!(delay > 0.0 && delay < 20e-9);
+    }
+
+    #[test]
+    fn sim_double_tail_delay() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_double_tail_delay");
+        let ctx = sky130_ctx();
+
+        // The outputs of the double-tail comparator reset low.
+        let delays = [dec!(0.01), dec!(-0.01)]
+            .into_iter()
+            .map(|overdrive| {
+                let measurement =
+                    measure_delay(&ctx, double_tail_dut(), dec!(0.9), overdrive, work_dir);
+                assert_eq!(
+                    measurement.outcome,
+                    ComparatorOutcome::Resolved(if overdrive > dec!(0) {
+                        ComparatorDecision::Pos
+                    } else {
+                        ComparatorDecision::Neg
+                    }),
+                    "comparator produced incorrect decision"
+                );
+                measurement
+                    .delay
+                    .expect("comparator output did not resolve")
+            })
+            .collect::<Vec<_>>();
+
+        assert!(delays.iter().all(|&delay| delay > 0.0 && delay < 20e-9));
+    }
+
+    #[test]
+    fn sim_strongarm_offset() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm_offset");
+        let ctx = sky130_ctx();
+        let tb = StrongArmOffsetTb {
+            dut: strongarm_dut(),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::default(),
+            tolerance: DecisionTolerance::default(),
+            search: OffsetSearch {
+                vcm: dec!(0.9),
+                max_offset: dec!(0.1),
+                resolution: dec!(0.0001),
+            },
+        };
+
+        let offset = tb
+            .run::<Spectre>(&ctx, work_dir)
+            .expect("failed to run offset search")
+            .expect("offset out of search range");
+
+        // Without mismatch, the comparator is symmetric.
+        assert!(offset.abs() <= dec!(0.0001));
+    }
+
+    #[test]
+    fn sim_strongarm_pvt_sweep() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm_pvt_sweep");
+        let ctx = sky130_ctx();
+        let sweep = StrongArmPvtSweep {
+            dut: strongarm_dut(),
+            corners: SKY130_CORNERS.to_vec(),
+            voltages: vec![dec!(1.62), dec!(1.8), dec!(1.98)],
+            temps: vec![dec!(-40.0), dec!(25.0), dec!(125.0)],
+            stimulus: StrongArmStimulus::default(),
+            tolerance: DecisionTolerance::default(),
+            threshold: DecisionThreshold::HalfSupply,
+            search: OffsetSearch {
+                vcm: dec!(0.9),
+                max_offset: dec!(0.1),
+                resolution: dec!(0.001),
+            },
+            overdrive: dec!(0.01),
+        };
+
+        let report = sweep.run::<Spectre>(&ctx, work_dir);
+
+        assert_eq!(report.len(), 45);
+        assert_eq!(
+            report.to_string().lines().count(),
+            46,
+            "report should contain a header and one row per PVT"
+        );
+        for (pvt, measurements) in report.iter() {
+            assert_eq!(
+                measurements.outcome,
+                ComparatorOutcome::Resolved(ComparatorDecision::Pos),
+                "incorrect decision at {pvt:?}"
+            );
+            assert!(measurements.delay.is_some(), "no decision delay at {pvt:?}");
+            assert!(
+                measurements
+                    .offset
+                    .is_some_and(|offset| offset.abs() <= dec!(0.001)),
+                "offset out of range at {pvt:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn sim_cap_trim_strongarm_offset() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_cap_trim_strongarm_offset"
+        );
+        let ctx = sky130_ctx();
+        let comparator = CapTrimStrongArmInstance {
+            strongarm: strongarm_dut(),
+            dac: CapTrimDac {
+                unit: MosParams {
+                    w: 420,
+                    l: 150,
+                    nf: 1,
+                },
+                bits: 4,
+            },
+        };
+
+        let offset = |code_p, code_n| {
+            StrongArmOffsetTb {
+                dut: FixedCapTrim {
+                    comparator,
+                    code_p,
+                    code_n,
+                },
+                pvt: Pvt {
+                    corner: Sky130Corner::Tt,
+                    voltage: dec!(1.8),
+                    temp: dec!(25.0),
+                },
+                stimulus: StrongArmStimulus::default(),
+                tolerance: DecisionTolerance::default(),
+                search: OffsetSearch {
+                    vcm: dec!(0.9),
+                    max_offset: dec!(0.1),
+                    resolution: dec!(0.0001),
+                },
+            }
+            .run::<Spectre>(&ctx, work_dir)
+            .expect("failed to run offset search")
+            .expect("offset out of search range")
+        };
+
+        assert!(offset(0, 0).abs() <= dec!(0.0001));
+        assert!(offset(0, 15) < dec!(0));
+        assert!(offset(15, 0) > dec!(0));
+    }
+
+    #[test]
+    fn cap_trim_rejects_pmos_input() {
+        let ctx = sky130_ctx();
+        let comparator = CapTrimStrongArmInstance {
+            strongarm: pmos_strongarm_dut(),
+            dac: CapTrimDac {
+                unit: MosParams {
+                    w: 420,
+                    l: 150,
+                    nf: 1,
+                },
+                bits: 4,
+            },
+        };
+
+        assert!(ctx.export_scir(comparator).is_err());
+    }
+
+    #[test]
+    fn sim_aux_trim_strongarm_offset() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_aux_trim_strongarm_offset"
+        );
+        let ctx = sky130_ctx();
+
+        let offset = |aux_w, trim_p, trim_n| {
+            StrongArmOffsetTb {
+                dut: FixedAuxTrim {
+                    comparator: AuxTrimStrongArmInstance {
+                        strongarm: strongarm_dut(),
+                        aux_pair: MosParams {
+                            w: aux_w,
+                            l: 1_000,
+                            nf: 1,
+                        },
+                    },
+                    trim_p,
+                    trim_n,
+                },
+                pvt: Pvt {
+                    corner: Sky130Corner::Tt,
+                    voltage: dec!(1.8),
+                    temp: dec!(25.0),
+                },
+                stimulus: StrongArmStimulus::default(),
+                tolerance: DecisionTolerance::default(),
+                search: OffsetSearch {
+                    vcm: dec!(0.9),
+                    max_offset: dec!(0.3),
+                    resolution: dec!(0.0001),
+                },
+            }
+            .run::<Spectre>(&ctx, work_dir)
+            .expect("failed to run offset search")
+            .expect("offset out of search range")
+        };
+
+        assert!(offset(420, Rail::Vss, Rail::Vss).abs() <= dec!(0.0001));
+
+        // A positive trim voltage shifts the offset negative, and vice versa.
+        let shift = offset(420, Rail::Vdd, Rail::Vss);
+        assert!(shift < dec!(0));
+        let reverse = offset(420, Rail::Vss, Rail::Vdd);
+        assert!(reverse > dec!(0));
+        assert!((shift + reverse).abs() <= shift.abs() / dec!(5));
+
+        // The shift scales roughly with `gm_aux`, and thus with the auxiliary pair width.
+        let ratio = offset(840, Rail::Vdd, Rail::Vss) / shift;
+        assert!(
+            ratio > dec!(1.5) && ratio < dec!(2.5),
+            "doubling the auxiliary pair width scaled the offset by {ratio}"
+        );
+    }
+
+    #[test]
+    fn sim_strongarm_calibration() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_strongarm_calibration"
+        );
+        let ctx = sky130_ctx();
+        let dac = IdealTrimDac {
+            bits: 6,
+            lsb: dec!(0.001),
+        };
+        let tb = StrongArmCalibrationTb {
+            dut: strongarm_dut(),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::default(),
+            tolerance: DecisionTolerance::default(),
+            dac,
+            offset: dec!(0.0203),
+            search: OffsetSearch {
+                vcm: dec!(0.9),
+                max_offset: dec!(0.01),
+                resolution: dec!(0.0001),
+            },
+        };
+
+        let result = tb
+            .run::<Spectre>(&ctx, work_dir)
+            .expect("failed to run calibration");
+
+        assert_eq!(result.code, dac.midscale().unwrap() + 20);
+        let residual = result
+            .residual
+            .expect("residual offset out of search range");
+        assert!(residual.abs() <= dac.lsb);
+    }
+
+    #[test]
+    fn ideal_trim_dac_range() {
+        let dac = |bits| IdealTrimDac {
+            bits,
+            lsb: dec!(0.001),
+        };
+        assert_eq!(dac(0).midscale(), None);
+        assert_eq!(dac(0).voltage(0), None);
+        assert_eq!(dac(1).midscale(), Some(1));
+        assert_eq!(dac(6).voltage(0), Some(dec!(-0.032)));
+        assert_eq!(dac(64).midscale(), Some(1 << 63));
+        assert_eq!(dac(65).midscale(), None);
+    }
+
+    #[test]
+    fn sim_strongarm_monte_carlo_offset() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_strongarm_monte_carlo_offset"
+        );
+        let ctx = sky130_ctx();
+        let tb = StrongArmMonteCarloOffsetTb {
+            dut: strongarm_dut(),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::default(),
+            tolerance: DecisionTolerance::default(),
+            search: OffsetSearch {
+                vcm: dec!(0.9),
+                max_offset: dec!(0.1),
+                resolution: dec!(0.0001),
+            },
+            models: MismatchModels::sky130_commercial(
+                std::env::var("SKY130_COMMERCIAL_PDK_ROOT")
+                    .expect("the SKY130_COMMERCIAL_PDK_ROOT environment variable must be set"),
+            ),
+            samples: 8,
+            seed: 1,
+        };
+
+        let offsets = tb
+            .run(&ctx, work_dir)
+            .expect("failed to run Monte Carlo offset");
+
+        assert_eq!(offsets.failed, 0);
+        assert_eq!(offsets.samples.len() + offsets.out_of_range, 8);
+        assert!(
+            offsets.sigma.is_some_and(|sigma| sigma > 0.0),
+            "mismatch should produce a nonzero offset spread"
+        );
+    }
+
+    #[test]
+    fn offset_search_many() {
+        let search = OffsetSearch {
+            vcm: dec!(0.9),
+            max_offset: dec!(0.1),
+            resolution: dec!(0.001),
+        };
+        let offsets = [dec!(0.0123), dec!(-0.0456), dec!(0.2)];
+        let mut simulations = 0;
+
+        let found = search.run_many(offsets.len(), |vinp, vinn| {
+            simulations += 1;
+            offsets
+                .iter()
+                .map(|&offset| {
+                    ComparatorOutcome::Resolved(if vinp - vinn > offset {
+                        ComparatorDecision::Pos
+                    } else {
+                        ComparatorDecision::Neg
+                    })
+                })
+                .collect()
+        });
+
+        assert!((found[0].unwrap() - offsets[0]).abs() <= search.resolution);
+        assert!((found[1].unwrap() - offsets[1]).abs() <= search.resolution);
+        assert_eq!(found[2], None);
+        // The range check and the first bisection step are shared by all searches.
+        assert!(simulations < 2 * 2 + 2 * 8);
+    }
+
+    #[test]
+    fn offset_search_many_failure() {
+        let search = OffsetSearch {
+            vcm: dec!(0.9),
+            max_offset: dec!(0.1),
+            resolution: dec!(0.001),
+        };
+        let offset = dec!(0.0123);
+
+        let found = search
+            .try_run_many(2, |vinp, vinn| {
+                let decision = ComparatorOutcome::Resolved(if vinp - vinn > offset {
+                    ComparatorDecision::Pos
+                } else {
+                    ComparatorDecision::Neg
+                });
+                // The second search fails once it narrows in on the offset.
+                let failure = if (vinp - vinn).abs() < dec!(0.05) {
+                    ComparatorOutcome::SimulationError("convergence failure".to_string())
+                } else {
+                    decision.clone()
+                };
+                vec![decision, failure]
+            })
+            .expect("invalid search parameters");
+
+        assert!((found[0].clone().unwrap().unwrap() - offset).abs() <= search.resolution);
+        assert!(matches!(
+            found[1],
+            Err(MeasurementError::SimulationError(_))
+        ));
+    }
+
+    #[test]
+    fn offset_search_invalid_params() {
+        let search = |max_offset, resolution| OffsetSearch {
+            vcm: dec!(0.9),
+            max_offset,
+            resolution,
+        };
+        let simulate =
+            |_: Decimal, _: Decimal| ComparatorOutcome::Resolved(ComparatorDecision::Pos);
+
+        for (max_offset, resolution) in [
+            (dec!(0.1), dec!(0)),
+            (dec!(0.1), dec!(-0.001)),
+            (dec!(0), dec!(0.001)),
+        ] {
+            assert!(matches!(
+                search(max_offset, resolution).try_run(simulate),
+                Err(MeasurementError::InvalidParams(_))
+            ));
+        }
+
+        // A resolution below the precision of `Decimal` at the offset still terminates.
+        let offset = search(dec!(10), Decimal::new(1, 28))
+            .try_run(|vinp, vinn| {
+                ComparatorOutcome::Resolved(if vinp - vinn > dec!(1.2345) {
+                    ComparatorDecision::Pos
+                } else {
+                    ComparatorDecision::Neg
+                })
+            })
+            .expect("search failed")
+            .expect("offset out of search range");
+        assert!((offset - dec!(1.2345)).abs() < dec!(1e-20));
+    }
+
+    #[test]
+    fn offset_distribution_statistics() {
+        let failure = || Err(MeasurementError::SimulationError("failed".to_string()));
+        let offsets = OffsetDistribution::from_samples([
+            Ok(Some(1e-3)),
+            Ok(None),
+            failure(),
+            Ok(Some(3e-3)),
+            Ok(Some(2e-3)),
+        ]);
+        assert_eq!(offsets.samples, vec![1e-3, 3e-3, 2e-3]);
+        assert_eq!(offsets.out_of_range, 1);
+        assert_eq!(offsets.failed, 1);
+        approx::assert_abs_diff_eq!(offsets.mean.unwrap(), 2e-3, epsilon = 1e-12);
+        approx::assert_abs_diff_eq!(offsets.sigma.unwrap(), 1e-3, epsilon = 1e-12);
+
+        let offsets = OffsetDistribution::from_samples([Ok(Some(1e-3)), Ok(None)]);
+        assert_eq!(offsets.mean, Some(1e-3));
+        assert_eq!(offsets.sigma, None);
+
+        let offsets = OffsetDistribution::from_samples([Ok(None), failure()]);
+        assert_eq!(offsets.mean, None);
+        assert_eq!(offsets.sigma, None);
+        assert_eq!(offsets.out_of_range, 1);
+        assert_eq!(offsets.failed, 1);
+    }
+
+    #[test]
+    fn sim_strongarm_noise() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm_noise");
+        let ctx = sky130_ctx();
+        let tb = StrongArmNoiseTb {
+            dut: strongarm_dut(),
+            vinp: dec!(0.9005),
+            vinn: dec!(0.8995),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::periodic(dec!(2e-9)),
+            tolerance: DecisionTolerance::default(),
+            cycles: 200,
+            noisefmax: dec!(20e9),
+        };
+
+        let noise = ctx
+            .simulate::<Spectre, _>(tb, work_dir)
+            .expect("failed to run simulation")
+            .expect("failed to measure noise");
+
+        assert_eq!(noise.pos + noise.neg + noise.unresolved, 200);
+        let sigma = noise
+            .sigma
+            .expect("differential input too large to estimate noise");
+        assert!(sigma > 0.0);
+    }
+
+    #[test]
+    fn inverse_normal_cdf_quantiles() {
+        for (p, z) in [
+            (0.5, 0.0),
+            (0.841_344_746_068_542_9, 1.0),
+            (0.975, 1.959_963_984_540_054),
+            (0.01, -2.326_347_874_040_841),
+            (0.999, 3.090_232_306_167_813_6),
+        ] {
+            approx::assert_abs_diff_eq!(inverse_normal_cdf(p), z, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn sim_strongarm_energy() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_strongarm_energy");
+        let ctx = sky130_ctx();
+        let tb = StrongArmEnergyTb {
+            dut: strongarm_dut(),
+            vinp: dec!(0.91),
+            vinn: dec!(0.89),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus {
+                stop: dec!(10e-9),
+                ..StrongArmStimulus::periodic(dec!(4e-9))
+            },
+        };
+
+        let energy = ctx
+            .simulate::<Spectre, _>(tb, work_dir)
+            .expect("failed to run simulation")
+            .expect("failed to measure energy")
+            .expect("simulation did not contain a complete clock period");
+
+        assert!(energy.evaluation > 0.0);
+        assert!(energy.reset > 0.0);
+        assert!(
+            energy.total < 1e-12,
+            "energy per decision should be well below 1 pJ"
+        );
+    }
+
+    #[test]
+    fn waveform_measurements() {
+        let t = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let v = [0.0, 1.0, 1.0, 0.0, 1.0];
+
+        assert_eq!(crossing(&t, &v, 0.5, true, 0.0), Some(0.5));
+        assert_eq!(crossing(&t, &v, 0.5, false, 0.0), Some(2.5));
+        assert_eq!(crossing(&t, &v, 0.5, true, 1.0), Some(3.5));
+        assert_eq!(crossing(&t, &v, 0.5, true, 3.6), None);
+        assert_eq!(crossing(&t, &v, 2.0, true, 0.0), None);
+
+        assert_eq!(integrate(&t, &v, 0.0, 4.0), 2.5);
+        assert_eq!(integrate(&t, &v, 0.5, 1.5), 0.875);
+        assert_eq!(integrate(&t, &v, 5.0, 6.0), 0.0);
+
+        let tolerance = DecisionTolerance::default();
+        assert_eq!(
+            classify(1.8, 0.0, 1.8, &tolerance),
+            ComparatorOutcome::Resolved(ComparatorDecision::Pos)
+        );
+        assert_eq!(
+            classify(0.0, 1.8, 1.8, &tolerance),
+            ComparatorOutcome::Resolved(ComparatorDecision::Neg)
+        );
+        assert_eq!(
+            classify(1.2, 0.3, 1.8, &tolerance),
+            ComparatorOutcome::Unrailed { vop: 1.2, von: 0.3 }
+        );
+        assert_eq!(
+            classify(0.9, 0.85, 1.8, &tolerance),
+            ComparatorOutcome::Metastable {
+                vop: 0.9,
+                von: 0.85
+            }
+        );
+    }
+
+    #[test]
+    fn sim_enabled_strongarm() {
+        let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/sim_enabled_strongarm");
+        let ctx = sky130_ctx();
+        let dut = FixedEnable {
+            comparator: EnabledStrongArmInstance {
+                strongarm: strongarm_dut(),
+                switch: MosParams {
+                    w: 10_000,
+                    l: 150,
+                    nf: 1,
+                },
+            },
+            enabled: true,
+        };
+        check_decisions::<Spectre, _>(&ctx, dut, work_dir);
+    }
+
+    #[test]
+    fn sim_disabled_strongarm_leakage() {
+        let work_dir = concat!(
             env!("CARGO_MANIFEST_DIR"),
-            "/build/layout_strongarm"
+            "/build/sim_disabled_strongarm_leakage"
+        );
+        let ctx = sky130_ctx();
+        let tb = StrongArmLeakageTb {
+            dut: FixedEnable {
+                comparator: EnabledStrongArmInstance {
+                    strongarm: strongarm_dut(),
+                    switch: MosParams {
+                        w: 10_000,
+                        l: 150,
+                        nf: 1,
+                    },
+                },
+                enabled: false,
+            },
+            vinp: dec!(0.91),
+            vinn: dec!(0.89),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::default(),
+        };
+
+        let leakage = ctx
+            .simulate::<Spectre, _>(tb, work_dir)
+            .expect("failed to run simulation")
+            .expect("failed to measure leakage");
+
+        assert!(
+            leakage.current < 1e-6,
+            "disabled comparator should draw less than 1 uA, drew {} A",
+            leakage.current
+        );
+        assert!(leakage.vop > 1.7, "positive output should be held high");
+        assert!(leakage.von > 1.7, "negative output should be held high");
+    }
+
+    #[test]
+    fn sim_invalid_leakage_stimulus() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_invalid_leakage_stimulus"
+        );
+        let ctx = sky130_ctx();
+        let tb = StrongArmLeakageTb {
+            dut: strongarm_dut(),
+            vinp: dec!(0.91),
+            vinn: dec!(0.89),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus {
+                delay: dec!(30e-9),
+                ..Default::default()
+            },
+        };
+
+        assert!(matches!(
+            ctx.simulate::<Spectre, _>(tb, work_dir)
+                .expect("failed to run simulation"),
+            Err(MeasurementError::InvalidParams(_))
         ));
-        let gds_path = work_dir.join("layout.gds");
-        let netlist_path = work_dir.join("netlist.sp");
+    }
+
+    #[test]
+    fn sim_strongarm_multicycle() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_strongarm_multicycle"
+        );
         let ctx = sky130_ctx();
+        let overdrives = [
+            dec!(0.1),
+            dec!(-0.1),
+            dec!(0.01),
+            dec!(-0.01),
+            dec!(-0.01),
+            dec!(0.001),
+        ];
+        let tb = StrongArmMultiCycleTb {
+            dut: strongarm_dut(),
+            pvt: Pvt {
+                corner: Sky130Corner::Tt,
+                voltage: dec!(1.8),
+                temp: dec!(25.0),
+            },
+            stimulus: StrongArmStimulus::periodic(dec!(4e-9)),
+            inputs: overdrives
+                .iter()
+                .map(|vdiff| (dec!(0.9) + vdiff / dec!(2), dec!(0.9) - vdiff / dec!(2)))
+                .collect(),
+            tolerance: DecisionTolerance::default(),
+        };
 
-        let block = TileWrapper::new(AtollStrongArmInstance {
-            half_tail_w: 1_250,
-            input_pair_w: 4_000,
-            inv_nmos_w: 2_000,
-            inv_pmos_w: 1_000,
-            precharge_w: 1_000,
+        let outcomes = ctx
+            .simulate::<Spectre, _>(tb, work_dir)
+            .expect("failed to run simulation");
+
+        assert_eq!(outcomes.len(), overdrives.len());
+        for (outcome, vdiff) in outcomes.into_iter().zip(overdrives) {
+            assert_eq!(
+                outcome,
+                ComparatorOutcome::Resolved(if vdiff > dec!(0) {
+                    ComparatorDecision::Pos
+                } else {
+                    ComparatorDecision::Neg
+                }),
+                "comparator produced incorrect decision"
+            );
+        }
+    }
+
+    #[test]
+    fn sim_latched_comparator_multicycle() {
+        let work_dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/sim_latched_comparator_multicycle"
+        );
+        let ctx = sky130_ctx();
+        let pvt = Pvt {
+            corner: Sky130Corner::Tt,
+            voltage: dec!(1.8),
+            temp: dec!(25.0),
+        };
+        let dut = LatchOutputs(LatchedComparator {
+            comparator: strongarm_dut(),
+            latch: SrLatch {
+                nmos: MosParams {
+                    w: 1_000,
+                    l: 150,
+                    nf: 1,
+                },
+                pmos: MosParams {
+                    w: 2_000,
+                    l: 150,
+                    nf: 1,
+                },
+            },
         });
+        let period = dec!(4e-9);
+        let inputs = |vdiff: Decimal| (dec!(0.9) + vdiff / dec!(2), dec!(0.9) - vdiff / dec!(2));
+        let expected = |vdiff: Decimal| {
+            ComparatorOutcome::Resolved(if vdiff > dec!(0) {
+                ComparatorDecision::Pos
+            } else {
+                ComparatorDecision::Neg
+            })
+        };
+
+        // q and qb take the decision of every evaluation phase.
+        let overdrives = [dec!(0.1), dec!(-0.1), dec!(0.01), dec!(-0.01)];
+        let outcomes = ctx
+            .simulate::<Spectre, _>(
+                StrongArmMultiCycleTb {
+                    dut,
+                    pvt,
+                    stimulus: StrongArmStimulus::periodic(period),
+                    inputs: overdrives.iter().copied().map(inputs).collect(),
+                    tolerance: DecisionTolerance::default(),
+                },
+                work_dir,
+            )
+            .expect("failed to run simulation");
+        assert_eq!(outcomes.len(), overdrives.len());
+        for (outcome, vdiff) in outcomes.into_iter().zip(overdrives) {
+            assert_eq!(outcome, expected(vdiff), "latch took incorrect decision");
+        }
+
+        // q and qb hold the decision through the reset phase after the second
+        // evaluation phase, while the comparator outputs are reset.
+        for vdiff in [dec!(0.01), dec!(-0.01)] {
+            let (vinp, vinn) = inputs(vdiff);
+            let outcome = ctx
+                .simulate::<Spectre, _>(
+                    StrongArmTranTb {
+                        dut,
+                        vinp,
+                        vinn,
+                        pvt,
+                        stimulus: StrongArmStimulus {
+                            stop: period * dec!(2.4),
+                            ..StrongArmStimulus::periodic(period)
+                        },
+                        tolerance: DecisionTolerance::default(),
+                    },
+                    work_dir,
+                )
+                .expect("failed to run simulation");
+            assert_eq!(outcome, expected(vdiff), "latch did not hold its decision");
+        }
+    }
+
+    /// Writes the netlist and layout of `block` to `build/<name>`, returning the
+    /// path of the netlist.
+    fn write_layout_and_netlist<B>(block: B, name: &str) -> PathBuf
+    where
+        B: Schematic<Sky130Pdk> + Layout<Sky130Pdk> + Clone,
+    {
+        let work_dir = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/build")).join(name);
+        let gds_path = work_dir.join("layout.gds");
+        let netlist_path = work_dir.join("netlist.sp");
+        let ctx = sky130_ctx();
 
         let scir = ctx
-            .export_scir(block)
+            .export_scir(block.clone())
             .unwrap()
             .scir
             .convert_schema::<Sky130CommercialSchema>()
@@ -343,10 +1872,273 @@ mod tests {
             .build()
             .unwrap();
         Spice
-            .write_scir_netlist_to_file(&scir, netlist_path, NetlistOptions::default())
+            .write_scir_netlist_to_file(&scir, &netlist_path, NetlistOptions::default())
             .expect("failed to write netlist");
 
         ctx.write_layout(block, gds_path)
             .expect("failed to write layout");
+
+        netlist_path
+    }
+
+    /// Returns the number of transistors in `netlist` with all four terminals tied
+    /// to the same net, for each such net, in ascending order.
+    fn dummy_counts(netlist: &str) -> Vec<usize> {
+        let mut counts = HashMap::<&str, usize>::new();
+        for line in netlist.lines() {
+            let tokens = line.split_whitespace().collect::<Vec<_>>();
+            if tokens.len() > 5
+                && tokens[5].contains("fet")
+                && tokens[1..5].iter().all(|net| *net == tokens[1])
+            {
+                *counts.entry(tokens[1]).or_default() += 1;
+            }
+        }
+        let mut counts = counts.into_values().collect::<Vec<_>>();
+        counts.sort();
+        counts
+    }
+
+    #[test]
+    fn layout_strongarm() {
+        let block = TileWrapper::new(AtollStrongArmInstance {
+            half_tail_w: 1_250,
+            input_pair_w: 4_000,
+            inv_nmos_w: 2_000,
+            inv_pmos_w: 1_000,
+            precharge_w: 1_000,
+            ..Default::default()
+        });
+        write_layout_and_netlist(block, "layout_strongarm");
+    }
+
+    #[test]
+    fn layout_folded_strongarm() {
+        write_layout_and_netlist(atoll_folded_strongarm_dut(), "layout_folded_strongarm");
+    }
+
+    #[test]
+    fn layout_common_centroid_strongarm() {
+        write_layout_and_netlist(
+            atoll_common_centroid_strongarm_dut(),
+            "layout_common_centroid_strongarm",
+        );
+    }
+
+    #[test]
+    fn layout_guard_ring_strongarm() {
+        let block = TileWrapper::new(AtollStrongArmInstance {
+            guard_rings: true,
+            ..Default::default()
+        });
+        write_layout_and_netlist(block, "layout_guard_ring_strongarm");
+    }
+
+    #[test]
+    fn layout_dummy_strongarm() {
+        let block = TileWrapper::new(AtollStrongArmInstance {
+            dummies: true,
+            ..Default::default()
+        });
+        let netlist =
+            std::fs::read_to_string(write_layout_and_netlist(block, "layout_dummy_strongarm"))
+                .expect("failed to read netlist");
+
+        // Three dummies in each of the two precharge rows and the PMOS inverter row,
+        // tied to VDD, and in the NMOS inverter and input pair rows, tied to VSS,
+        // along with two dummies in the tail row.
+        assert_eq!(dummy_counts(&netlist), [8, 9]);
+    }
+
+    #[test]
+    fn layout_boundary_tap_strongarm() {
+        let block = TileWrapper::new(AtollStrongArmInstance {
+            boundary_taps: BoundaryTaps {
+                ntap_rows: 1,
+                ptap_rows: 1,
+            },
+            ..Default::default()
+        });
+        write_layout_and_netlist(block, "layout_boundary_tap_strongarm");
+    }
+
+    #[test]
+    fn layout_edge_pin_strongarm() {
+        let block = TileWrapper::new(AtollStrongArmInstance {
+            pins: ComparatorPins {
+                clock: PinPlacement::Edge(EdgePin {
+                    edge: Side::Bot,
+                    layer: PinLayer::Met2,
+                    track: 10,
+                    width: 200,
+                }),
+                input_p: PinPlacement::Edge(EdgePin {
+                    edge: Side::Left,
+                    layer: PinLayer::Met1,
+                    track: 20,
+                    width: 200,
+                }),
+                input_n: PinPlacement::Edge(EdgePin {
+                    edge: Side::Right,
+                    layer: PinLayer::Met1,
+                    track: 20,
+                    width: 200,
+                }),
+                output_p: PinPlacement::Edge(EdgePin {
+                    edge: Side::Top,
+                    layer: PinLayer::Met2,
+                    track: 10,
+                    width: 200,
+                }),
+                output_n: PinPlacement::Edge(EdgePin {
+                    edge: Side::Top,
+                    layer: PinLayer::Met2,
+                    track: 20,
+                    width: 200,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        write_layout_and_netlist(block, "layout_edge_pin_strongarm");
+    }
+
+    #[test]
+    fn layout_invalid_edge_pin_strongarm() {
+        let gds_path = PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/layout_invalid_edge_pin_strongarm/layout.gds"
+        ));
+        let ctx = sky130_ctx();
+        let edge_pin = |track, width| {
+            TileWrapper::new(AtollStrongArmInstance {
+                pins: ComparatorPins {
+                    clock: PinPlacement::Edge(EdgePin {
+                        edge: Side::Bot,
+                        layer: PinLayer::Met2,
+                        track,
+                        width,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+
+        assert!(
+            ctx.write_layout(edge_pin(10, 201), &gds_path).is_err(),
+            "odd pin width was accepted"
+        );
+        assert!(
+            ctx.write_layout(edge_pin(-1, 200), &gds_path).is_err(),
+            "pin track outside the edge was accepted"
+        );
+    }
+
+    #[test]
+    fn layout_power_strap_strongarm() {
+        let block = TileWrapper::new(AtollStrongArmInstance {
+            top_layer: Metal::Met4,
+            straps: Some(PowerStraps {
+                width: 4,
+                pitch: 16,
+            }),
+            ..Default::default()
+        });
+        write_layout_and_netlist(block, "layout_power_strap_strongarm");
+    }
+
+    #[test]
+    fn layout_invalid_power_strap_strongarm() {
+        let gds_path = PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/layout_invalid_power_strap_strongarm/layout.gds"
+        ));
+        let ctx = sky130_ctx();
+        let straps = |top_layer, width, pitch, pins| {
+            TileWrapper::new(AtollStrongArmInstance {
+                top_layer,
+                straps: Some(PowerStraps { width, pitch }),
+                pins,
+                ..Default::default()
+            })
+        };
+        let met2_clock = ComparatorPins {
+            clock: PinPlacement::Edge(EdgePin {
+                edge: Side::Bot,
+                layer: PinLayer::Met2,
+                track: 10,
+                width: 200,
+            }),
+            ..Default::default()
+        };
+
+        assert!(
+            ctx.write_layout(straps(Metal::Met2, 4, 16, Default::default()), &gds_path)
+                .is_err(),
+            "straps beneath met3 were accepted"
+        );
+        assert!(
+            ctx.write_layout(straps(Metal::Met4, 0, 16, Default::default()), &gds_path)
+                .is_err(),
+            "zero strap width was accepted"
+        );
+        assert!(
+            ctx.write_layout(straps(Metal::Met4, 4, 7, Default::default()), &gds_path)
+                .is_err(),
+            "strap pitch below twice the width was accepted"
+        );
+        assert!(
+            ctx.write_layout(straps(Metal::Met3, 4, 16, met2_clock), &gds_path)
+                .is_err(),
+            "met2 edge pin beneath met2 straps was accepted"
+        );
+    }
+
+    #[test]
+    fn layout_double_tail() {
+        let work_dir = PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/build/layout_double_tail"
+        ));
+        let gds_path = work_dir.join("layout.gds");
+        let ctx = sky130_ctx();
+
+        ctx.write_layout(atoll_double_tail_dut(), gds_path)
+            .expect("failed to write layout");
+    }
+
+    #[test]
+    fn layout_latched_strongarm() {
+        let block = TileWrapper::new(AtollLatchedComparator {
+            comparator: AtollStrongArmInstance::default(),
+            latch: AtollSrLatch {
+                nmos_w: 1_000,
+                pmos_w: 1_000,
+                top_layer: Metal::Met2,
+            },
+        });
+        write_layout_and_netlist(block, "layout_latched_strongarm");
+    }
+
+    #[test]
+    fn layout_aux_trim_strongarm() {
+        let block = TileWrapper::new(AtollAuxTrimStrongArmInstance {
+            strongarm: AtollStrongArmInstance::default(),
+            aux_pair_w: 1_000,
+            trim_p_pin: PinPlacement::Auto,
+            trim_n_pin: PinPlacement::Auto,
+        });
+        write_layout_and_netlist(block, "layout_aux_trim_strongarm");
+    }
+
+    #[test]
+    fn layout_enabled_strongarm() {
+        let block = TileWrapper::new(AtollEnabledStrongArmInstance {
+            strongarm: AtollStrongArmInstance::default(),
+            switch_w: 2_500,
+            enable_pin: PinPlacement::Auto,
+        });
+        write_layout_and_netlist(block, "layout_enabled_strongarm");
     }
 }
@@ -1,4 +1,5 @@
 use approx::abs_diff_eq;
+use ngspice::Ngspice;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -8,15 +9,31 @@ use sky130pdk::Sky130Pdk;
 use spectre::analysis::tran::Tran;
 use spectre::blocks::{Pulse, Vsource};
 use spectre::{ErrPreset, Spectre};
+use std::fmt;
 use substrate::block::Block;
-use substrate::io::schematic::{Bundle, HardwareType, Node};
-use substrate::io::{DiffPair, TestbenchIo};
+use substrate::io::schematic::{Bundle, HardwareType, Node, Terminal};
+use substrate::io::{DiffPair, TestbenchIo, TwoTerminalIo};
 use substrate::pdk::corner::Pvt;
-use substrate::schematic::{Cell, CellBuilder, ExportsNestedData, NestedData, Schematic};
+use substrate::schematic::primitives::{Capacitor, Resistor};
+use substrate::schematic::schema::FromSchema;
+use substrate::schematic::{
+    Cell, CellBuilder, ExportsNestedData, NestedData, NestedView, Schematic,
+};
 use substrate::simulation::data::{tran, FromSaved, Save, SaveTb};
+use substrate::simulation::options::Temperature;
+use substrate::simulation::waveform::Waveform;
 use substrate::simulation::{SimController, SimulationContext, Simulator, Testbench};
 
-use crate::ClockedDiffComparatorIo;
+use crate::{ClockedDiffComparatorIo, InvalidParams};
+
+pub mod calibration;
+pub mod energy;
+pub mod leakage;
+pub mod montecarlo;
+pub mod multicycle;
+pub mod noise;
+pub mod offset;
+pub mod pvt;
 
 #[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
 #[substrate(io = "TestbenchIo")]
@@ -25,6 +42,74 @@ pub struct StrongArmTranTb<T> {
     pub vinp: Decimal,
     pub vinn: Decimal,
     pub pvt: Pvt<Sky130Corner>,
+    pub stimulus: StrongArmStimulus,
+    pub tolerance: DecisionTolerance,
+}
+
+/// The stimulus and simulation settings used to exercise a comparator.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StrongArmStimulus {
+    /// The clock period.
+    pub period: Decimal,
+    /// The fraction of the clock period spent at the high level,
+    /// excluding the rise and fall times.
+    pub duty_cycle: Decimal,
+    /// The delay before the first rising edge of the clock.
+    pub delay: Decimal,
+    /// The clock rise time.
+    pub rise: Decimal,
+    /// The clock fall time.
+    pub fall: Decimal,
+    /// The transient simulation stop time.
+    pub stop: Decimal,
+    /// The Spectre error preset.
+    pub errpreset: ErrPreset,
+    /// The ngspice transient step size.
+    pub step: Decimal,
+    /// The capacitance from each comparator output to ground.
+    ///
+    /// No load capacitors are instantiated if zero.
+    pub load: Decimal,
+    /// The resistance in series with each input source.
+    ///
+    /// The input sources are connected directly to the comparator if zero.
+    pub source_resistance: Decimal,
+}
+
+impl Default for StrongArmStimulus {
+    /// A single rising clock edge at 10 ns, followed by 20 ns of evaluation.
+    fn default() -> Self {
+        Self {
+            period: dec!(1000),
+            duty_cycle: dec!(0.1),
+            delay: dec!(10e-9),
+            rise: dec!(100e-12),
+            fall: dec!(100e-12),
+            stop: dec!(30e-9),
+            errpreset: ErrPreset::Conservative,
+            step: dec!(10e-12),
+            load: dec!(0),
+            source_resistance: dec!(0),
+        }
+    }
+}
+
+impl StrongArmStimulus {
+    /// A 50% duty cycle clock that starts low and rises halfway through each `period`.
+    ///
+    /// The stop time is set to the end of the first period.
+    pub fn periodic(period: Decimal) -> Self {
+        let edge = dec!(100e-12);
+        Self {
+            period,
+            duty_cycle: dec!(0.5) - edge / period,
+            delay: period / dec!(2),
+            rise: edge,
+            fall: edge,
+            stop: period,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, NestedData)]
@@ -34,9 +119,14 @@ pub struct StrongArmTranTbNodes {
     vinn: Node,
     vinp: Node,
     clk: Node,
+    /// The positive terminal of the supply source.
+    vdd: Terminal,
 }
 
-trait Dut: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk> + Clone {}
+/// A comparator that can be placed in the testbenches in this module.
+///
+/// Implemented for every Sky130 block with a [`ClockedDiffComparatorIo`].
+pub trait Dut: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk> + Clone {}
 impl<T: Block<Io = ClockedDiffComparatorIo> + Schematic<Sky130Pdk> + Clone> Dut for T {}
 
 impl<T: Dut> ExportsNestedData for StrongArmTranTb<T> {
@@ -49,52 +139,167 @@ impl<T: Dut> Schematic<Spectre> for StrongArmTranTb<T> {
         io: &<<Self as Block>::Io as HardwareType>::Bundle,
         cell: &mut CellBuilder<Spectre>,
     ) -> substrate::error::Result<Self::NestedData> {
-        let dut = cell
-            .sub_builder::<Sky130Pdk>()
-            .instantiate(self.dut.clone());
-
-        let vinp = cell.instantiate(Vsource::dc(self.vinp));
-        let vinn = cell.instantiate(Vsource::dc(self.vinn));
-        let vdd = cell.instantiate(Vsource::dc(self.pvt.voltage));
-        let vclk = cell.instantiate(Vsource::pulse(Pulse {
+        tb_schematic(
+            &self.dut,
+            Spectre::dc(self.vinp),
+            Spectre::dc(self.vinn),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+impl<T: Dut> Schematic<Ngspice> for StrongArmTranTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Ngspice>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        tb_schematic(
+            &self.dut,
+            Ngspice::dc(self.vinp),
+            Ngspice::dc(self.vinn),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+/// A simulator that can run the comparator testbenches in this module.
+trait TbSimulator: Simulator + FromSchema<Sky130Pdk> {
+    /// The simulator's independent voltage source.
+    type Vsource: Block<Io = TwoTerminalIo> + Schematic<Self> + Clone;
+
+    /// A DC voltage source of `value` volts.
+    fn dc(value: Decimal) -> Self::Vsource;
+
+    /// The clock source described by `stimulus`, switching between 0 and `vdd`.
+    fn clock(stimulus: &StrongArmStimulus, vdd: Decimal) -> Self::Vsource;
+
+    /// A piecewise-linear voltage source.
+    fn pwl(waveform: Waveform<Decimal>) -> Self::Vsource;
+}
+
+impl TbSimulator for Spectre {
+    type Vsource = Vsource;
+
+    fn dc(value: Decimal) -> Self::Vsource {
+        Vsource::dc(value)
+    }
+
+    fn clock(stimulus: &StrongArmStimulus, vdd: Decimal) -> Self::Vsource {
+        Vsource::pulse(Pulse {
             val0: dec!(0),
-            val1: self.pvt.voltage,
-            period: Some(dec!(1000)),
-            width: Some(dec!(100)),
-            delay: Some(dec!(10e-9)),
-            rise: Some(dec!(100e-12)),
-            fall: Some(dec!(100e-12)),
-        }));
-
-        cell.connect(io.vss, vinp.io().n);
-        cell.connect(io.vss, vinn.io().n);
-        cell.connect(io.vss, vdd.io().n);
-        cell.connect(io.vss, vclk.io().n);
-
-        let output = cell.signal("output", DiffPair::default());
-
-        cell.connect(
-            Bundle::<ClockedDiffComparatorIo> {
-                input: Bundle::<DiffPair> {
-                    p: *vinp.io().p,
-                    n: *vinn.io().p,
-                },
-                output: output.clone(),
-                clock: *vclk.io().p,
-                vdd: *vdd.io().p,
-                vss: io.vss,
-            },
-            dut.io(),
-        );
-
-        Ok(StrongArmTranTbNodes {
-            vop: output.p,
-            von: output.n,
-            vinn: *vinn.io().p,
-            vinp: *vinp.io().p,
-            clk: *vclk.io().p,
+            val1: vdd,
+            period: Some(stimulus.period),
+            width: Some(stimulus.period * stimulus.duty_cycle),
+            delay: Some(stimulus.delay),
+            rise: Some(stimulus.rise),
+            fall: Some(stimulus.fall),
+        })
+    }
+
+    fn pwl(waveform: Waveform<Decimal>) -> Self::Vsource {
+        Vsource::pwl(waveform)
+    }
+}
+
+impl TbSimulator for Ngspice {
+    type Vsource = ngspice::blocks::Vsource;
+
+    fn dc(value: Decimal) -> Self::Vsource {
+        ngspice::blocks::Vsource::dc(value)
+    }
+
+    fn clock(stimulus: &StrongArmStimulus, vdd: Decimal) -> Self::Vsource {
+        ngspice::blocks::Vsource::pulse(ngspice::blocks::Pulse {
+            val0: dec!(0),
+            val1: vdd,
+            period: Some(stimulus.period),
+            width: Some(stimulus.period * stimulus.duty_cycle),
+            delay: Some(stimulus.delay),
+            rise: Some(stimulus.rise),
+            fall: Some(stimulus.fall),
+            num_pulses: None,
         })
     }
+
+    fn pwl(waveform: Waveform<Decimal>) -> Self::Vsource {
+        ngspice::blocks::Vsource::pwl(waveform)
+    }
+}
+
+/// Instantiates `dut` along with the given input sources, a DC supply of `vdd`,
+/// and the clock, load, and source resistance described by `stimulus`.
+fn tb_schematic<S, T>(
+    dut: &T,
+    vinp: S::Vsource,
+    vinn: S::Vsource,
+    vdd: Decimal,
+    stimulus: &StrongArmStimulus,
+    io: &Bundle<TestbenchIo>,
+    cell: &mut CellBuilder<S>,
+) -> substrate::error::Result<StrongArmTranTbNodes>
+where
+    S: TbSimulator,
+    T: Dut,
+    Resistor: Schematic<S>,
+    Capacitor: Schematic<S>,
+{
+    let dut = cell.sub_builder::<Sky130Pdk>().instantiate(dut.clone());
+
+    let vinp = cell.instantiate(vinp);
+    let vinn = cell.instantiate(vinn);
+    let vclk = cell.instantiate(S::clock(stimulus, vdd));
+    let vdd = cell.instantiate(S::dc(vdd));
+
+    for source in [&vinp, &vinn, &vclk, &vdd] {
+        cell.connect(io.vss, source.io().n);
+    }
+
+    let input = cell.signal("input", DiffPair::default());
+    for (source, input) in [(*vinp.io().p, input.p), (*vinn.io().p, input.n)] {
+        if stimulus.source_resistance.is_zero() {
+            cell.connect(source, input);
+        } else {
+            let res = cell.instantiate(Resistor::new(stimulus.source_resistance));
+            cell.connect(res.io().p, source);
+            cell.connect(res.io().n, input);
+        }
+    }
+
+    let output = cell.signal("output", DiffPair::default());
+    if !stimulus.load.is_zero() {
+        for output in [output.p, output.n] {
+            let cap = cell.instantiate(Capacitor::new(stimulus.load));
+            cell.connect(cap.io().p, output);
+            cell.connect(cap.io().n, io.vss);
+        }
+    }
+
+    cell.connect(
+        Bundle::<ClockedDiffComparatorIo> {
+            input: input.clone(),
+            output: output.clone(),
+            clock: *vclk.io().p,
+            vdd: *vdd.io().p,
+            vss: io.vss,
+        },
+        dut.io(),
+    );
+
+    Ok(StrongArmTranTbNodes {
+        vop: output.p,
+        von: output.n,
+        vinn: input.n,
+        vinp: input.p,
+        clk: *vclk.io().p,
+        vdd: vdd.io().p,
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromSaved)]
@@ -105,6 +310,8 @@ pub struct ComparatorSim {
     vinn: tran::Voltage,
     vinp: tran::Voltage,
     clk: tran::Voltage,
+    /// The current into the positive terminal of the supply source.
+    idd: tran::Current,
 }
 
 /// The decision made by a comparator.
@@ -120,50 +327,539 @@ pub enum ComparatorDecision {
     Pos,
 }
 
+/// The tolerances used to classify comparator outputs.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DecisionTolerance {
+    /// The maximum distance of each output from its rail for the outputs
+    /// to be considered railed.
+    pub rail: Decimal,
+    /// The separation between `vop` and `von` below which unrailed outputs
+    /// are considered metastable.
+    pub metastable: Decimal,
+}
+
+impl Default for DecisionTolerance {
+    fn default() -> Self {
+        Self {
+            rail: dec!(1e-4),
+            metastable: dec!(0.1),
+        }
+    }
+}
+
+/// The outcome of a comparator simulation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ComparatorOutcome {
+    /// The outputs railed, resolving to the given decision.
+    Resolved(ComparatorDecision),
+    /// The outputs separated but did not rail.
+    ///
+    /// Typically indicates a correct but slow decision.
+    Unrailed {
+        /// The final positive output voltage.
+        vop: f64,
+        /// The final negative output voltage.
+        von: f64,
+    },
+    /// The outputs did not separate.
+    Metastable {
+        /// The final positive output voltage.
+        vop: f64,
+        /// The final negative output voltage.
+        von: f64,
+    },
+    /// The simulator failed.
+    SimulationError(String),
+}
+
+impl ComparatorOutcome {
+    /// Returns the decision if the outputs railed.
+    pub fn decision(&self) -> Option<ComparatorDecision> {
+        match self {
+            Self::Resolved(decision) => Some(*decision),
+            _ => None,
+        }
+    }
+
+    /// Returns the decision if the outputs railed, or the decision implied by the
+    /// sign of `vop - von` if the outputs separated without railing.
+    pub fn likely_decision(&self) -> Option<ComparatorDecision> {
+        match self {
+            Self::Resolved(decision) => Some(*decision),
+            Self::Unrailed { vop, von } => Some(if vop > von {
+                ComparatorDecision::Pos
+            } else {
+                ComparatorDecision::Neg
+            }),
+            Self::Metastable { .. } | Self::SimulationError(_) => None,
+        }
+    }
+}
+
+impl ComparatorSim {
+    /// Returns the outcome of the comparator at the end of the simulation.
+    pub fn outcome(&self, vdd: f64, tolerance: &DecisionTolerance) -> ComparatorOutcome {
+        classify(
+            *self.vop.last().unwrap(),
+            *self.von.last().unwrap(),
+            vdd,
+            tolerance,
+        )
+    }
+
+    /// Returns the outcome of the comparator during each evaluation phase
+    /// (i.e. each period in which the clock is high).
+    ///
+    /// The outputs are sampled at the last time point before the clock falls,
+    /// or at the end of the simulation if the clock does not fall.
+    pub fn outcomes(&self, vdd: f64, tolerance: &DecisionTolerance) -> Vec<ComparatorOutcome> {
+        let mut outcomes = Vec::new();
+        let mut start = 0.0;
+        while let Some(rise) = crossing(&self.t, &self.clk, vdd / 2.0, true, start) {
+            let fall = crossing(&self.t, &self.clk, vdd / 2.0, false, rise);
+            let idx = match fall {
+                Some(fall) => self.t.iter().rposition(|&t| t < fall).unwrap(),
+                None => self.t.len() - 1,
+            };
+            outcomes.push(classify(self.vop[idx], self.von[idx], vdd, tolerance));
+            match fall {
+                Some(fall) => start = fall,
+                None => break,
+            }
+        }
+        outcomes
+    }
+
+    /// Returns the time of the first rising edge of the clock, measured at 50% of `vdd`.
+    pub fn clock_edge(&self, vdd: f64) -> Option<f64> {
+        crossing(&self.t, &self.clk, vdd / 2.0, true, 0.0)
+    }
+
+    /// Returns whether the outputs are closer to `vdd` than to ground at the first
+    /// rising edge of the clock, i.e. whether the comparator resets its outputs high.
+    pub fn resets_high(&self, vdd: f64) -> Option<bool> {
+        let start = self.clock_edge(vdd)?;
+        let idx = self.t.iter().position(|&t| t >= start)?;
+        Some(self.vop[idx] + self.von[idx] > vdd)
+    }
+
+    /// Returns the time at which the comparator output first satisfies `threshold`
+    /// after the first rising edge of the clock.
+    pub fn decision_time(&self, vdd: f64, threshold: DecisionThreshold) -> Option<f64> {
+        let start = self.clock_edge(vdd)?;
+        match threshold {
+            DecisionThreshold::HalfSupply => {
+                let resets_high = self.resets_high(vdd)?;
+                let vfirst = self
+                    .vop
+                    .iter()
+                    .zip(self.von.iter())
+                    .map(|(vop, von)| {
+                        if resets_high {
+                            vop.min(*von)
+                        } else {
+                            vop.max(*von)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                crossing(&self.t, &vfirst, vdd / 2.0, !resets_high, start)
+            }
+            DecisionThreshold::Separation(sep) => {
+                let vdiff = self
+                    .vop
+                    .iter()
+                    .zip(self.von.iter())
+                    .map(|(vop, von)| (vop - von).abs())
+                    .collect::<Vec<_>>();
+                crossing(&self.t, &vdiff, sep.to_f64().unwrap(), true, start)
+            }
+        }
+    }
+
+    /// Returns the clock-to-output delay of the comparator in seconds.
+    ///
+    /// The delay is measured from the 50% point of the first rising clock edge
+    /// to the time at which the outputs satisfy `threshold`.
+    pub fn delay(&self, vdd: f64, threshold: DecisionThreshold) -> Option<f64> {
+        Some(self.decision_time(vdd, threshold)? - self.clock_edge(vdd)?)
+    }
+
+    /// Returns the energy drawn from the supply between times `t0` and `t1`, in joules.
+    pub fn supply_energy(&self, vdd: f64, t0: f64, t1: f64) -> f64 {
+        // Current flows out of the positive terminal of the supply source.
+        -vdd * integrate(&self.t, &self.idd, t0, t1)
+    }
+
+    /// Returns the average current drawn from the supply between times `t0` and `t1`, in amps.
+    ///
+    /// Returns an error unless `t0 < t1`.
+    pub fn supply_current(&self, t0: f64, t1: f64) -> Result<f64, InvalidParams> {
+        if t0.is_nan() || t1.is_nan() || t0 >= t1 {
+            return Err(InvalidParams(format!(
+                "supply current interval must have positive length, got {t0} to {t1}"
+            )));
+        }
+        Ok(-integrate(&self.t, &self.idd, t0, t1) / (t1 - t0))
+    }
+
+    /// Returns the energy consumed by the first complete clock period of the simulation.
+    ///
+    /// The period begins at the first rising edge of the clock. Returns [`None`] if
+    /// the simulation does not contain a complete clock period.
+    pub fn energy(&self, vdd: f64) -> Option<ComparisonEnergy> {
+        let rise = crossing(&self.t, &self.clk, vdd / 2.0, true, 0.0)?;
+        let fall = crossing(&self.t, &self.clk, vdd / 2.0, false, rise)?;
+        let next_rise = crossing(&self.t, &self.clk, vdd / 2.0, true, fall)?;
+
+        let evaluation = self.supply_energy(vdd, rise, fall);
+        let reset = self.supply_energy(vdd, fall, next_rise);
+
+        Some(ComparisonEnergy {
+            total: evaluation + reset,
+            evaluation,
+            reset,
+        })
+    }
+}
+
+impl Save<Spectre, Tran, NestedView<StrongArmTranTbNodes>> for ComparatorSim {
+    fn save(
+        ctx: &SimulationContext<Spectre>,
+        nodes: NestedView<StrongArmTranTbNodes>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <Self as FromSaved<Spectre, Tran>>::SavedKey {
+        ComparatorSimSavedKey {
+            t: tran::Time::save(ctx, (), opts),
+            vop: tran::Voltage::save(ctx, nodes.vop, opts),
+            von: tran::Voltage::save(ctx, nodes.von, opts),
+            vinn: tran::Voltage::save(ctx, nodes.vinn, opts),
+            vinp: tran::Voltage::save(ctx, nodes.vinp, opts),
+            clk: tran::Voltage::save(ctx, nodes.clk, opts),
+            idd: tran::Current::save(ctx, nodes.vdd, opts),
+        }
+    }
+}
+
+impl Save<Ngspice, ngspice::tran::Tran, NestedView<StrongArmTranTbNodes>> for ComparatorSim {
+    fn save(
+        ctx: &SimulationContext<Ngspice>,
+        nodes: NestedView<StrongArmTranTbNodes>,
+        opts: &mut <Ngspice as Simulator>::Options,
+    ) -> <Self as FromSaved<Ngspice, ngspice::tran::Tran>>::SavedKey {
+        ComparatorSimSavedKey {
+            t: tran::Time::save(ctx, (), opts),
+            vop: tran::Voltage::save(ctx, nodes.vop, opts),
+            von: tran::Voltage::save(ctx, nodes.von, opts),
+            vinn: tran::Voltage::save(ctx, nodes.vinn, opts),
+            vinp: tran::Voltage::save(ctx, nodes.vinp, opts),
+            clk: tran::Voltage::save(ctx, nodes.clk, opts),
+            idd: tran::Current::save(ctx, nodes.vdd, opts),
+        }
+    }
+}
+
 impl<T: Dut> SaveTb<Spectre, Tran, ComparatorSim> for StrongArmTranTb<T> {
     fn save_tb(
         ctx: &SimulationContext<Spectre>,
         cell: &Cell<Self>,
         opts: &mut <Spectre as Simulator>::Options,
     ) -> <ComparatorSim as FromSaved<Spectre, Tran>>::SavedKey {
-        ComparatorSimSavedKey {
-            t: tran::Time::save(ctx, (), opts),
-            vop: tran::Voltage::save(ctx, cell.data().vop, opts),
-            von: tran::Voltage::save(ctx, cell.data().von, opts),
-            vinn: tran::Voltage::save(ctx, cell.data().vinn, opts),
-            vinp: tran::Voltage::save(ctx, cell.data().vinp, opts),
-            clk: tran::Voltage::save(ctx, cell.data().clk, opts),
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> SaveTb<Ngspice, ngspice::tran::Tran, ComparatorSim> for StrongArmTranTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Ngspice>,
+        cell: &Cell<Self>,
+        opts: &mut <Ngspice as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Ngspice, ngspice::tran::Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> StrongArmTranTb<T> {
+    /// Runs the transient simulation of this testbench in Spectre.
+    fn simulate_spectre<B>(
+        &self,
+        sim: &SimController<Spectre, B>,
+    ) -> Result<ComparatorSim, <Spectre as Simulator>::Error>
+    where
+        B: Testbench<Spectre> + SaveTb<Spectre, Tran, ComparatorSim>,
+    {
+        let mut opts = spectre::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        sim.simulate(opts, self.spectre_tran())
+    }
+
+    /// The Spectre transient analysis run by this testbench.
+    fn spectre_tran(&self) -> Tran {
+        Tran {
+            stop: self.stimulus.stop,
+            start: None,
+            errpreset: Some(self.stimulus.errpreset),
+            noisefmax: None,
+            noisefmin: None,
+        }
+    }
+
+    /// Runs the transient simulation of this testbench in ngspice.
+    fn simulate_ngspice<B>(
+        &self,
+        sim: &SimController<Ngspice, B>,
+    ) -> Result<ComparatorSim, <Ngspice as Simulator>::Error>
+    where
+        B: Testbench<Ngspice> + SaveTb<Ngspice, ngspice::tran::Tran, ComparatorSim>,
+    {
+        let mut opts = ngspice::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        sim.simulate(
+            opts,
+            ngspice::tran::Tran {
+                step: self.stimulus.step,
+                stop: self.stimulus.stop,
+                start: None,
+            },
+        )
+    }
+
+    /// Classifies the result of a simulation of this testbench.
+    fn outcome<E: std::fmt::Display>(&self, wav: Result<ComparatorSim, E>) -> ComparatorOutcome {
+        match wav {
+            Ok(wav) => wav.outcome(self.pvt.voltage.to_f64().unwrap(), &self.tolerance),
+            Err(err) => ComparatorOutcome::SimulationError(err.to_string()),
         }
     }
 }
 
 impl<T: Dut> Testbench<Spectre> for StrongArmTranTb<T> {
-    type Output = Option<ComparatorDecision>;
+    type Output = ComparatorOutcome;
 
     fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
-        let mut opts = spectre::Options::default();
-        sim.set_option(self.pvt.corner, &mut opts);
-        let wav: ComparatorSim = sim
-            .simulate(
-                opts,
-                Tran {
-                    stop: dec!(30e-9),
-                    start: None,
-                    errpreset: Some(ErrPreset::Conservative),
-                },
-            )
-            .expect("failed to run simulation");
-
-        let von = *wav.von.last().unwrap();
-        let vop = *wav.vop.last().unwrap();
-
-        let vdd = self.pvt.voltage.to_f64().unwrap();
-        if abs_diff_eq!(von, 0.0, epsilon = 1e-4) && abs_diff_eq!(vop, vdd, epsilon = 1e-4) {
-            Some(ComparatorDecision::Pos)
-        } else if abs_diff_eq!(von, vdd, epsilon = 1e-4) && abs_diff_eq!(vop, 0.0, epsilon = 1e-4) {
-            Some(ComparatorDecision::Neg)
-        } else {
-            None
+        self.outcome(self.simulate_spectre(&sim))
+    }
+}
+
+impl<T: Dut> Testbench<Ngspice> for StrongArmTranTb<T> {
+    type Output = ComparatorOutcome;
+
+    fn run(&self, sim: SimController<Ngspice, Self>) -> Self::Output {
+        self.outcome(self.simulate_ngspice(&sim))
+    }
+}
+
+/// The criterion used to determine when a comparator has made its decision.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum DecisionThreshold {
+    /// The first of `vop` and `von` to leave its reset level crosses 50% of VDD.
+    ///
+    /// The reset level is taken from the outputs at the first rising clock edge.
+    /// Outputs that reset high, as in the NMOS-input
+    /// [`StrongArmInstance`](crate::StrongArmInstance), are measured on the falling
+    /// crossing of the lower output. Outputs that reset low, as in the PMOS-input
+    /// variant and the [`DoubleTailInstance`](crate::DoubleTailInstance), are measured
+    /// on the rising crossing of the higher output.
+    #[default]
+    HalfSupply,
+    /// `vop` and `von` are separated by at least the given voltage.
+    Separation(Decimal),
+}
+
+/// A testbench that measures the clock-to-output delay of a comparator.
+///
+/// Uses the same stimulus as [`StrongArmTranTb`].
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "TestbenchIo")]
+pub struct StrongArmDelayTb<T> {
+    pub tb: StrongArmTranTb<T>,
+    pub threshold: DecisionThreshold,
+}
+
+/// The result of a [`StrongArmDelayTb`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DelayMeasurement {
+    /// The outcome of the comparator at the end of the simulation.
+    pub outcome: ComparatorOutcome,
+    /// The clock-to-output delay in seconds.
+    ///
+    /// [`None`] if the outputs never satisfied the threshold or the simulation failed.
+    pub delay: Option<f64>,
+}
+
+impl<T: Dut> StrongArmDelayTb<T> {
+    /// Measures the outcome and delay from the result of a simulation of this testbench.
+    fn measure<E: std::fmt::Display>(&self, wav: Result<ComparatorSim, E>) -> DelayMeasurement {
+        let delay = wav
+            .as_ref()
+            .ok()
+            .and_then(|wav| wav.delay(self.tb.pvt.voltage.to_f64().unwrap(), self.threshold));
+        DelayMeasurement {
+            outcome: self.tb.outcome(wav),
+            delay,
         }
     }
 }
+
+impl<T: Dut> ExportsNestedData for StrongArmDelayTb<T> {
+    type NestedData = StrongArmTranTbNodes;
+}
+
+impl<T: Dut> Schematic<Spectre> for StrongArmDelayTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        <StrongArmTranTb<T> as Schematic<Spectre>>::schematic(&self.tb, io, cell)
+    }
+}
+
+impl<T: Dut> Schematic<Ngspice> for StrongArmDelayTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Ngspice>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        <StrongArmTranTb<T> as Schematic<Ngspice>>::schematic(&self.tb, io, cell)
+    }
+}
+
+impl<T: Dut> SaveTb<Spectre, Tran, ComparatorSim> for StrongArmDelayTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Spectre>,
+        cell: &Cell<Self>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Spectre, Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> SaveTb<Ngspice, ngspice::tran::Tran, ComparatorSim> for StrongArmDelayTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Ngspice>,
+        cell: &Cell<Self>,
+        opts: &mut <Ngspice as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Ngspice, ngspice::tran::Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> Testbench<Spectre> for StrongArmDelayTb<T> {
+    type Output = DelayMeasurement;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        self.measure(self.tb.simulate_spectre(&sim))
+    }
+}
+
+impl<T: Dut> Testbench<Ngspice> for StrongArmDelayTb<T> {
+    type Output = DelayMeasurement;
+
+    fn run(&self, sim: SimController<Ngspice, Self>) -> Self::Output {
+        self.measure(self.tb.simulate_ngspice(&sim))
+    }
+}
+
+/// An error that prevented a measurement testbench from producing a result.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeasurementError {
+    /// The testbench parameters are invalid.
+    InvalidParams(String),
+    /// The simulator failed.
+    SimulationError(String),
+}
+
+impl fmt::Display for MeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParams(err) => write!(f, "invalid parameters: {err}"),
+            Self::SimulationError(err) => write!(f, "failed to run simulation: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for MeasurementError {}
+
+impl From<InvalidParams> for MeasurementError {
+    fn from(value: InvalidParams) -> Self {
+        Self::InvalidParams(value.0)
+    }
+}
+
+/// The energy consumed by a comparator during one clock period.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonEnergy {
+    /// The total energy per decision, in joules.
+    pub total: f64,
+    /// The energy consumed while the clock is high, in joules.
+    pub evaluation: f64,
+    /// The energy consumed while the clock is low, in joules.
+    pub reset: f64,
+}
+
+/// Classifies the comparator output voltages `vop` and `von`.
+pub(crate) fn classify(
+    vop: f64,
+    von: f64,
+    vdd: f64,
+    tolerance: &DecisionTolerance,
+) -> ComparatorOutcome {
+    let rail = tolerance.rail.to_f64().unwrap();
+    if abs_diff_eq!(von, 0.0, epsilon = rail) && abs_diff_eq!(vop, vdd, epsilon = rail) {
+        ComparatorOutcome::Resolved(ComparatorDecision::Pos)
+    } else if abs_diff_eq!(von, vdd, epsilon = rail) && abs_diff_eq!(vop, 0.0, epsilon = rail) {
+        ComparatorOutcome::Resolved(ComparatorDecision::Neg)
+    } else if (vop - von).abs() < tolerance.metastable.to_f64().unwrap() {
+        ComparatorOutcome::Metastable { vop, von }
+    } else {
+        ComparatorOutcome::Unrailed { vop, von }
+    }
+}
+
+/// Returns the first time at or after `start` at which `values` crosses `level`
+/// in the given direction.
+///
+/// Linearly interpolates between simulation time points.
+pub(crate) fn crossing(
+    t: &[f64],
+    values: &[f64],
+    level: f64,
+    rising: bool,
+    start: f64,
+) -> Option<f64> {
+    t.windows(2)
+        .zip(values.windows(2))
+        .filter(|(t, _)| t[1] >= start)
+        .find_map(|(t, v)| {
+            let crossed = if rising {
+                v[0] < level && v[1] >= level
+            } else {
+                v[0] > level && v[1] <= level
+            };
+            if !crossed {
+                return None;
+            }
+            let tc = t[0] + (level - v[0]) * (t[1] - t[0]) / (v[1] - v[0]);
+            (tc >= start).then_some(tc)
+        })
+}
+
+/// Integrates `values` over time from `t0` to `t1` using the trapezoidal rule.
+///
+/// Linearly interpolates between simulation time points.
+pub(crate) fn integrate(t: &[f64], values: &[f64], t0: f64, t1: f64) -> f64 {
+    t.windows(2)
+        .zip(values.windows(2))
+        .map(|(t, v)| {
+            let a = t[0].max(t0);
+            let b = t[1].min(t1);
+            if a >= b {
+                return 0.0;
+            }
+            let interp = |x: f64| v[0] + (v[1] - v[0]) * (x - t[0]) / (t[1] - t[0]);
+            (interp(a) + interp(b)) / 2.0 * (b - a)
+        })
+        .sum()
+}
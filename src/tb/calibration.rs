@@ -0,0 +1,176 @@
+//! Behavioral model of a comparator offset calibration loop.
+
+use std::fmt;
+use std::path::Path;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use sky130pdk::Sky130Pdk;
+use substrate::context::PdkContext;
+use substrate::pdk::corner::Pvt;
+use substrate::simulation::{Simulator, Testbench};
+
+use super::offset::OffsetSearch;
+use super::{
+    ComparatorDecision, ComparatorOutcome, DecisionTolerance, Dut, MeasurementError,
+    StrongArmStimulus, StrongArmTranTb,
+};
+
+/// An ideal offset trim DAC.
+///
+/// Code `c` injects a voltage of `(c - 2^(bits - 1)) * lsb` in series with the
+/// positive input of the comparator, so that the midscale code injects nothing.
+/// The DAC is not part of the comparator: testbenches model it by shifting the
+/// `vinp` they apply, so it neither loads the comparator nor models any trim circuit.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct IdealTrimDac {
+    /// The number of bits in the trim code, from 1 to 64.
+    pub bits: u32,
+    /// The voltage injected per code step.
+    pub lsb: Decimal,
+}
+
+impl IdealTrimDac {
+    /// Returns the midscale code, or [`None`] if `bits` is not between 1 and 64.
+    pub fn midscale(&self) -> Option<u64> {
+        (1..=u64::BITS)
+            .contains(&self.bits)
+            .then(|| 1 << (self.bits - 1))
+    }
+
+    /// Returns the voltage injected by `code`, or [`None`] if `bits` is not between 1 and 64.
+    pub fn voltage(&self, code: u64) -> Option<Decimal> {
+        Some((Decimal::from(code) - Decimal::from(self.midscale()?)) * self.lsb)
+    }
+}
+
+/// A testbench that calibrates the offset of a comparator using an [`IdealTrimDac`].
+///
+/// The comparator inputs are shorted to the common mode of `search`, and a
+/// successive-approximation search over the trim code is run using one
+/// [`StrongArmTranTb`] simulation per bit. The residual offset at the final code
+/// is then measured with `search`.
+///
+/// No separate offset-injection source is instantiated. An ideal DC source in
+/// series with the positive input is equivalent to adding its voltage to the
+/// positive input source, so each simulation is a plain [`StrongArmTranTb`] with
+/// `vinp` shifted by the trim voltage of the code under test, less `offset`.
+/// "Shorted" inputs therefore means `vinp` and `vinn` both start at the common
+/// mode of `search` before this shift is applied.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StrongArmCalibrationTb<T> {
+    pub dut: T,
+    pub pvt: Pvt<Sky130Corner>,
+    pub stimulus: StrongArmStimulus,
+    pub tolerance: DecisionTolerance,
+    pub dac: IdealTrimDac,
+    /// An additional input-referred offset to emulate, in volts.
+    ///
+    /// Modeled by shifting the `vinp` applied by the testbench, so that
+    /// the decision of an offset-free comparator flips at `vinp - vinn = offset`.
+    pub offset: Decimal,
+    pub search: OffsetSearch,
+}
+
+/// The result of a [`StrongArmCalibrationTb`].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationResult {
+    /// The final trim code.
+    pub code: u64,
+    /// The voltage injected by the final trim code.
+    pub trim: Decimal,
+    /// The input-referred offset remaining with the final trim code applied,
+    /// or [`None`] if it lies outside of the search range.
+    pub residual: Option<Decimal>,
+}
+
+/// An error that prevented a [`StrongArmCalibrationTb`] from completing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalibrationError {
+    /// The trim DAC does not have between 1 and 64 bits.
+    InvalidBits(u32),
+    /// The residual offset search parameters are invalid.
+    InvalidSearch(String),
+    /// The simulator failed.
+    SimulationError(String),
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBits(bits) => {
+                write!(f, "trim DAC must have between 1 and 64 bits, got {bits}")
+            }
+            Self::InvalidSearch(err) => write!(f, "invalid offset search: {err}"),
+            Self::SimulationError(err) => write!(f, "failed to run simulation: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+impl<T: Dut> StrongArmCalibrationTb<T> {
+    /// Runs the calibration using simulator `S`.
+    ///
+    /// If the comparator is metastable when testing a bit, the bit is kept.
+    pub fn run<S>(
+        &self,
+        ctx: &PdkContext<Sky130Pdk>,
+        work_dir: impl AsRef<Path>,
+    ) -> Result<CalibrationResult, CalibrationError>
+    where
+        S: Simulator,
+        StrongArmTranTb<T>: Testbench<S, Output = ComparatorOutcome>,
+    {
+        if self.dac.midscale().is_none() {
+            return Err(CalibrationError::InvalidBits(self.dac.bits));
+        }
+        self.search
+            .validate()
+            .map_err(|err| CalibrationError::InvalidSearch(err.0))?;
+        let trim = |code: u64| self.dac.voltage(code).expect("DAC bits were validated");
+
+        let simulate = |vinp: Decimal, vinn: Decimal, code: u64| {
+            ctx.simulate::<S, _>(
+                StrongArmTranTb {
+                    dut: self.dut.clone(),
+                    vinp: vinp - self.offset + trim(code),
+                    vinn,
+                    pvt: self.pvt,
+                    stimulus: self.stimulus,
+                    tolerance: self.tolerance,
+                },
+                work_dir.as_ref(),
+            )
+            .unwrap_or_else(|err| ComparatorOutcome::SimulationError(err.to_string()))
+        };
+
+        let mut code = 0;
+        for bit in (0..self.dac.bits).rev() {
+            let trial = code | (1 << bit);
+            let outcome = simulate(self.search.vcm, self.search.vcm, trial);
+            if let ComparatorOutcome::SimulationError(err) = outcome {
+                return Err(CalibrationError::SimulationError(err));
+            }
+            // A positive decision with shorted inputs means the trim is too large.
+            if outcome.likely_decision() != Some(ComparatorDecision::Pos) {
+                code = trial;
+            }
+        }
+
+        let residual = self
+            .search
+            .try_run(|vinp, vinn| simulate(vinp, vinn, code))
+            .map_err(|err| match err {
+                MeasurementError::InvalidParams(err) => CalibrationError::InvalidSearch(err),
+                MeasurementError::SimulationError(err) => CalibrationError::SimulationError(err),
+            })?;
+
+        Ok(CalibrationResult {
+            code,
+            trim: trim(code),
+            residual,
+        })
+    }
+}
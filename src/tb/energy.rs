@@ -0,0 +1,102 @@
+//! Energy-per-comparison measurement.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use spectre::analysis::tran::Tran;
+use spectre::blocks::Vsource;
+use spectre::Spectre;
+use substrate::block::Block;
+use substrate::io::schematic::HardwareType;
+use substrate::io::TestbenchIo;
+use substrate::pdk::corner::Pvt;
+use substrate::schematic::{Cell, CellBuilder, ExportsNestedData, Schematic};
+use substrate::simulation::data::{FromSaved, Save, SaveTb};
+use substrate::simulation::options::Temperature;
+use substrate::simulation::{SimController, SimulationContext, Simulator, Testbench};
+
+use super::{
+    tb_schematic, ComparatorSim, ComparisonEnergy, Dut, MeasurementError, StrongArmStimulus,
+    StrongArmTranTbNodes,
+};
+
+/// A testbench that measures the energy consumed by a comparator per decision.
+///
+/// Clocks the comparator with `stimulus` and measures the supply energy drawn
+/// during the first complete clock period, which contains one evaluation phase and
+/// the subsequent reset phase. The stop time of `stimulus` must be after the
+/// second rising edge of the clock.
+///
+/// Only the energy drawn from the VDD source is measured. The energy delivered by
+/// the clock and input sources, including the clock driver's charging of the
+/// comparator's clock input capacitance, is excluded.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "TestbenchIo")]
+pub struct StrongArmEnergyTb<T> {
+    pub dut: T,
+    pub vinp: Decimal,
+    pub vinn: Decimal,
+    pub pvt: Pvt<Sky130Corner>,
+    pub stimulus: StrongArmStimulus,
+}
+
+impl<T: Dut> ExportsNestedData for StrongArmEnergyTb<T> {
+    type NestedData = StrongArmTranTbNodes;
+}
+
+impl<T: Dut> Schematic<Spectre> for StrongArmEnergyTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        tb_schematic(
+            &self.dut,
+            Vsource::dc(self.vinp),
+            Vsource::dc(self.vinn),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+impl<T: Dut> SaveTb<Spectre, Tran, ComparatorSim> for StrongArmEnergyTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Spectre>,
+        cell: &Cell<Self>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Spectre, Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> Testbench<Spectre> for StrongArmEnergyTb<T> {
+    /// The energy per decision, or [`None`] if the simulation did not contain
+    /// a complete clock period.
+    ///
+    /// Returns an error if the simulator failed.
+    type Output = Result<Option<ComparisonEnergy>, MeasurementError>;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        let mut opts = spectre::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        let wav: ComparatorSim = sim
+            .simulate(
+                opts,
+                Tran {
+                    stop: self.stimulus.stop,
+                    start: None,
+                    errpreset: Some(self.stimulus.errpreset),
+                    noisefmax: None,
+                    noisefmin: None,
+                },
+            )
+            .map_err(|err| MeasurementError::SimulationError(err.to_string()))?;
+
+        Ok(wav.energy(self.pvt.voltage.to_f64().unwrap()))
+    }
+}
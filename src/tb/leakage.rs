@@ -0,0 +1,121 @@
+//! Static supply current measurement.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use spectre::analysis::tran::Tran;
+use spectre::blocks::Vsource;
+use spectre::Spectre;
+use substrate::block::Block;
+use substrate::io::schematic::HardwareType;
+use substrate::io::TestbenchIo;
+use substrate::pdk::corner::Pvt;
+use substrate::schematic::{Cell, CellBuilder, ExportsNestedData, Schematic};
+use substrate::simulation::data::{FromSaved, Save, SaveTb};
+use substrate::simulation::options::Temperature;
+use substrate::simulation::{SimController, SimulationContext, Simulator, Testbench};
+
+use super::{
+    tb_schematic, ComparatorSim, Dut, MeasurementError, StrongArmStimulus, StrongArmTranTbNodes,
+};
+
+/// A testbench that measures the average supply current of a comparator.
+///
+/// Intended for measuring the leakage of a disabled comparator, such as a
+/// [`FixedEnable`](crate::enable::FixedEnable) with `enabled` set to `false`.
+/// The current is averaged from the first rising edge of the clock, at
+/// `stimulus.delay`, to the end of the simulation, so `stimulus.delay` must be
+/// less than `stimulus.stop`.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "TestbenchIo")]
+pub struct StrongArmLeakageTb<T> {
+    pub dut: T,
+    pub vinp: Decimal,
+    pub vinn: Decimal,
+    pub pvt: Pvt<Sky130Corner>,
+    pub stimulus: StrongArmStimulus,
+}
+
+/// The result of a [`StrongArmLeakageTb`].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LeakageMeasurement {
+    /// The average current drawn from the supply, in amps.
+    pub current: f64,
+    /// The final positive output voltage.
+    pub vop: f64,
+    /// The final negative output voltage.
+    pub von: f64,
+}
+
+impl<T: Dut> ExportsNestedData for StrongArmLeakageTb<T> {
+    type NestedData = StrongArmTranTbNodes;
+}
+
+impl<T: Dut> Schematic<Spectre> for StrongArmLeakageTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        tb_schematic(
+            &self.dut,
+            Vsource::dc(self.vinp),
+            Vsource::dc(self.vinn),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+impl<T: Dut> SaveTb<Spectre, Tran, ComparatorSim> for StrongArmLeakageTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Spectre>,
+        cell: &Cell<Self>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Spectre, Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> Testbench<Spectre> for StrongArmLeakageTb<T> {
+    /// The leakage measurement, or an error if the stimulus does not contain a clock
+    /// edge before the stop time or the simulator failed.
+    type Output = Result<LeakageMeasurement, MeasurementError>;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        let (t0, t1) = (
+            self.stimulus.delay.to_f64().unwrap(),
+            self.stimulus.stop.to_f64().unwrap(),
+        );
+        if t0 >= t1 {
+            return Err(MeasurementError::InvalidParams(format!(
+                "the clock delay of {t0} s must be less than the stop time of {t1} s"
+            )));
+        }
+
+        let mut opts = spectre::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        let wav: ComparatorSim = sim
+            .simulate(
+                opts,
+                Tran {
+                    stop: self.stimulus.stop,
+                    start: None,
+                    errpreset: Some(self.stimulus.errpreset),
+                    noisefmax: None,
+                    noisefmin: None,
+                },
+            )
+            .map_err(|err| MeasurementError::SimulationError(err.to_string()))?;
+
+        Ok(LeakageMeasurement {
+            current: wav.supply_current(t0, t1)?,
+            vop: *wav.vop.last().unwrap(),
+            von: *wav.von.last().unwrap(),
+        })
+    }
+}
@@ -0,0 +1,251 @@
+//! Monte Carlo mismatch characterization.
+
+use std::path::{Path, PathBuf};
+
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use sky130pdk::Sky130Pdk;
+use spectre::analysis::montecarlo::{self, MonteCarlo, Variations};
+use spectre::analysis::tran::Tran;
+use spectre::Spectre;
+use substrate::block::Block;
+use substrate::context::PdkContext;
+use substrate::io::schematic::HardwareType;
+use substrate::io::TestbenchIo;
+use substrate::pdk::corner::Pvt;
+use substrate::schematic::{Cell, CellBuilder, ExportsNestedData, Schematic};
+use substrate::simulation::data::{FromSaved, Save, SaveTb};
+use substrate::simulation::options::Temperature;
+use substrate::simulation::{SimController, SimulationContext, Simulator, Testbench};
+
+use super::offset::OffsetSearch;
+use super::{
+    ComparatorOutcome, ComparatorSim, DecisionTolerance, Dut, MeasurementError, StrongArmStimulus,
+    StrongArmTranTb, StrongArmTranTbNodes,
+};
+
+/// A model library section that declares the statistical mismatch of a process.
+///
+/// Spectre only samples the mismatch variations declared by the included models,
+/// so this section is included alongside the models of the simulated corner.
+///
+/// The statistical models cannot be selected through the [`Sky130Corner`] option of
+/// [`Pvt`]: that option only includes the process corner section (`tt`, `ss`, ...)
+/// of the Sky130 model library and has no statistical variant. The mismatch section
+/// is therefore included separately, on top of the corner set by [`Pvt`].
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MismatchModels {
+    /// The path to the model library.
+    pub lib: PathBuf,
+    /// The library section to include.
+    pub section: String,
+}
+
+impl MismatchModels {
+    /// The Monte Carlo mismatch models of the Sky130 commercial PDK installed at `pdk_root`.
+    ///
+    /// Includes the `mc` section of the same model library that the corner option
+    /// includes its corner sections from.
+    pub fn sky130_commercial(pdk_root: impl AsRef<Path>) -> Self {
+        Self {
+            lib: pdk_root
+                .as_ref()
+                .join("MODELS/SPECTRE/s8phirs_10r/Models/design_wrapper.lib.scs"),
+            section: "mc".to_string(),
+        }
+    }
+}
+
+/// A [`StrongArmTranTb`] simulated over a set of Monte Carlo mismatch samples.
+///
+/// Runs a single Monte Carlo analysis of `samples` iterations. Testbenches with the
+/// same `seed` see the same device mismatch in each iteration, regardless of the
+/// stimulus applied by `tb`.
+#[derive(Serialize, Deserialize, Block, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "TestbenchIo")]
+pub struct StrongArmMismatchTb<T> {
+    pub tb: StrongArmTranTb<T>,
+    /// The models that declare the sampled mismatch.
+    pub models: MismatchModels,
+    /// The Monte Carlo random seed.
+    pub seed: u64,
+    /// The number of Monte Carlo samples.
+    pub samples: usize,
+}
+
+impl<T: Dut> ExportsNestedData for StrongArmMismatchTb<T> {
+    type NestedData = StrongArmTranTbNodes;
+}
+
+impl<T: Dut> Schematic<Spectre> for StrongArmMismatchTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        <StrongArmTranTb<T> as Schematic<Spectre>>::schematic(&self.tb, io, cell)
+    }
+}
+
+impl<T: Dut> SaveTb<Spectre, MonteCarlo<Tran>, montecarlo::Output<ComparatorSim>>
+    for StrongArmMismatchTb<T>
+{
+    fn save_tb(
+        ctx: &SimulationContext<Spectre>,
+        cell: &Cell<Self>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <montecarlo::Output<ComparatorSim> as FromSaved<Spectre, MonteCarlo<Tran>>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> Testbench<Spectre> for StrongArmMismatchTb<T> {
+    /// The outcome of each Monte Carlo sample.
+    type Output = Vec<ComparatorOutcome>;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        let mut opts = spectre::Options::default();
+        sim.set_option(self.tb.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.tb.pvt.temp), &mut opts);
+        opts.include_section(self.models.lib.clone(), self.models.section.clone());
+        let wav: Result<montecarlo::Output<ComparatorSim>, _> = sim.simulate(
+            opts,
+            MonteCarlo {
+                variations: Variations::Mismatch,
+                numruns: self.samples,
+                seed: Some(self.seed),
+                firstrun: None,
+                analysis: self.tb.spectre_tran(),
+            },
+        );
+
+        match wav {
+            Ok(wav) => {
+                let vdd = self.tb.pvt.voltage.to_f64().unwrap();
+                (0..self.samples)
+                    .map(|i| wav[i].outcome(vdd, &self.tb.tolerance))
+                    .collect()
+            }
+            Err(err) => vec![ComparatorOutcome::SimulationError(err.to_string()); self.samples],
+        }
+    }
+}
+
+/// Monte Carlo characterization of the input-referred offset of a comparator.
+///
+/// Runs an [`OffsetSearch`] on each of `samples` mismatch samples. Each step of
+/// the search simulates all samples in a single Monte Carlo analysis.
+/// Only supported in Spectre.
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StrongArmMonteCarloOffsetTb<T> {
+    pub dut: T,
+    pub pvt: Pvt<Sky130Corner>,
+    pub stimulus: StrongArmStimulus,
+    pub tolerance: DecisionTolerance,
+    pub search: OffsetSearch,
+    /// The models that declare the sampled mismatch.
+    pub models: MismatchModels,
+    /// The number of Monte Carlo samples.
+    pub samples: usize,
+    /// The Monte Carlo random seed.
+    pub seed: u64,
+}
+
+/// The distribution of input-referred offsets over a set of Monte Carlo samples.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OffsetDistribution {
+    /// The mean offset, in volts.
+    ///
+    /// [`None`] if no sample was within the search range.
+    pub mean: Option<f64>,
+    /// The sample standard deviation of the offset, in volts.
+    ///
+    /// [`None`] if fewer than two samples were within the search range.
+    pub sigma: Option<f64>,
+    /// The offset of each sample whose offset was within the search range, in volts.
+    pub samples: Vec<f64>,
+    /// The number of samples whose offset was outside of the search range.
+    pub out_of_range: usize,
+    /// The number of samples whose search failed due to a simulation failure.
+    pub failed: usize,
+}
+
+impl OffsetDistribution {
+    /// Computes the distribution of the given offset samples.
+    ///
+    /// Samples that are [`None`] are counted as out of range, and samples that are
+    /// errors are counted as failed. Neither contributes to the statistics.
+    pub fn from_samples(
+        samples: impl IntoIterator<Item = Result<Option<f64>, MeasurementError>>,
+    ) -> Self {
+        let mut out_of_range = 0;
+        let mut failed = 0;
+        let samples = samples
+            .into_iter()
+            .filter_map(|sample| match sample {
+                Ok(Some(sample)) => Some(sample),
+                Ok(None) => {
+                    out_of_range += 1;
+                    None
+                }
+                Err(_) => {
+                    failed += 1;
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let n = samples.len() as f64;
+        let mean = (!samples.is_empty()).then(|| samples.iter().sum::<f64>() / n);
+        let sigma = mean.filter(|_| samples.len() > 1).map(|mean| {
+            (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        });
+
+        Self {
+            mean,
+            sigma,
+            samples,
+            out_of_range,
+            failed,
+        }
+    }
+}
+
+impl<T: Dut> StrongArmMonteCarloOffsetTb<T> {
+    /// Returns the distribution of the input-referred offset of the comparator.
+    ///
+    /// Samples whose search hits a simulation failure are counted as failed rather
+    /// than aborting the characterization. Returns an error if the search parameters
+    /// are invalid.
+    pub fn run(
+        &self,
+        ctx: &PdkContext<Sky130Pdk>,
+        work_dir: impl AsRef<Path>,
+    ) -> Result<OffsetDistribution, MeasurementError> {
+        let offsets = self.search.try_run_many(self.samples, |vinp, vinn| {
+            ctx.simulate::<Spectre, _>(
+                StrongArmMismatchTb {
+                    tb: StrongArmTranTb {
+                        dut: self.dut.clone(),
+                        vinp,
+                        vinn,
+                        pvt: self.pvt,
+                        stimulus: self.stimulus,
+                        tolerance: self.tolerance,
+                    },
+                    models: self.models.clone(),
+                    seed: self.seed,
+                    samples: self.samples,
+                },
+                work_dir.as_ref(),
+            )
+            .unwrap_or_else(|err| {
+                vec![ComparatorOutcome::SimulationError(err.to_string()); self.samples]
+            })
+        })?;
+        Ok(OffsetDistribution::from_samples(offsets.into_iter().map(
+            |offset| offset.map(|offset| offset.map(|offset| offset.to_f64().unwrap())),
+        )))
+    }
+}
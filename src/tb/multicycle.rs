@@ -0,0 +1,235 @@
+//! Multi-cycle clocked simulation.
+
+use ngspice::Ngspice;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use spectre::analysis::tran::Tran;
+use spectre::Spectre;
+use substrate::block::Block;
+use substrate::io::schematic::HardwareType;
+use substrate::io::TestbenchIo;
+use substrate::pdk::corner::Pvt;
+use substrate::schematic::{Cell, CellBuilder, ExportsNestedData, Schematic};
+use substrate::simulation::data::{FromSaved, Save, SaveTb};
+use substrate::simulation::options::Temperature;
+use substrate::simulation::waveform::Waveform;
+use substrate::simulation::{SimController, SimulationContext, Simulator, Testbench};
+
+use crate::InvalidParams;
+
+use super::{
+    tb_schematic, ComparatorOutcome, ComparatorSim, DecisionTolerance, Dut, StrongArmStimulus,
+    StrongArmTranTbNodes, TbSimulator,
+};
+
+/// A testbench that clocks a comparator for several cycles with a different
+/// input in each cycle.
+///
+/// Cycle `i` is evaluated on the `i`-th rising edge of the clock described by
+/// `stimulus`. The inputs of cycle `i + 1` start to change halfway through the
+/// reset phase that follows the evaluation phase of cycle `i`, once the clock
+/// has fully fallen, so each input is held constant from the preceding reset
+/// phase through the end of its evaluation phase. The input transitions must
+/// fit within the reset phase.
+#[derive(Serialize, Deserialize, Block, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "TestbenchIo")]
+pub struct StrongArmMultiCycleTb<T> {
+    pub dut: T,
+    pub pvt: Pvt<Sky130Corner>,
+    /// The clock and simulation settings.
+    ///
+    /// The input transitions use the clock rise and fall times. The stop time is
+    /// ignored; the simulation ends at the end of the last evaluation phase.
+    pub stimulus: StrongArmStimulus,
+    /// The `(vinp, vinn)` input voltages applied during each cycle.
+    ///
+    /// Must contain at least one entry; generating the schematic fails otherwise.
+    pub inputs: Vec<(Decimal, Decimal)>,
+    pub tolerance: DecisionTolerance,
+}
+
+impl<T> StrongArmMultiCycleTb<T> {
+    /// The piecewise-linear waveform for one input.
+    ///
+    /// Returns an error if there are no inputs.
+    fn waveform(
+        &self,
+        input: impl Fn(&(Decimal, Decimal)) -> Decimal,
+    ) -> Result<Waveform<Decimal>, InvalidParams> {
+        let values = self.inputs.iter().map(input).collect::<Vec<_>>();
+        let first = *values.first().ok_or_else(|| {
+            InvalidParams("a multi-cycle testbench requires at least one input".to_string())
+        })?;
+
+        let mut waveform = Waveform::new();
+        waveform.push(dec!(0), first);
+        for (i, v) in values.windows(2).enumerate() {
+            if v[1] == v[0] {
+                continue;
+            }
+            let t = self.transition(i);
+            let edge = if v[1] > v[0] {
+                self.stimulus.rise
+            } else {
+                self.stimulus.fall
+            };
+            waveform.push(t, v[0]);
+            waveform.push(t + edge, v[1]);
+        }
+        Ok(waveform)
+    }
+
+    /// The time at which the inputs start to change from those of cycle `i`
+    /// to those of cycle `i + 1`.
+    ///
+    /// This is the midpoint of the reset phase between the falling edge that ends
+    /// the evaluation phase of cycle `i` and the next rising edge.
+    fn transition(&self, i: usize) -> Decimal {
+        let StrongArmStimulus {
+            period,
+            duty_cycle,
+            delay,
+            rise,
+            fall,
+            ..
+        } = self.stimulus;
+        let fallen = rise + period * duty_cycle + fall;
+        delay + period * Decimal::from(i) + (fallen + period) / dec!(2)
+    }
+
+    /// The total simulation time, ending as the clock starts to fall after the
+    /// evaluation phase of the last cycle.
+    fn stop(&self) -> Decimal {
+        let StrongArmStimulus {
+            period,
+            duty_cycle,
+            delay,
+            rise,
+            ..
+        } = self.stimulus;
+        let last = Decimal::from(self.inputs.len().saturating_sub(1));
+        delay + period * last + rise + period * duty_cycle
+    }
+
+    /// Classifies each cycle of a simulation of this testbench.
+    ///
+    /// If the simulation failed, every cycle has a [`ComparatorOutcome::SimulationError`].
+    fn outcomes<E: std::fmt::Display>(
+        &self,
+        wav: Result<ComparatorSim, E>,
+    ) -> Vec<ComparatorOutcome> {
+        match wav {
+            Ok(wav) => wav.outcomes(self.pvt.voltage.to_f64().unwrap(), &self.tolerance),
+            Err(err) => {
+                vec![ComparatorOutcome::SimulationError(err.to_string()); self.inputs.len()]
+            }
+        }
+    }
+}
+
+impl<T: Dut> ExportsNestedData for StrongArmMultiCycleTb<T> {
+    type NestedData = StrongArmTranTbNodes;
+}
+
+impl<T: Dut> Schematic<Spectre> for StrongArmMultiCycleTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        tb_schematic(
+            &self.dut,
+            Spectre::pwl(self.waveform(|(vinp, _)| *vinp)?),
+            Spectre::pwl(self.waveform(|(_, vinn)| *vinn)?),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+impl<T: Dut> Schematic<Ngspice> for StrongArmMultiCycleTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Ngspice>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        tb_schematic(
+            &self.dut,
+            Ngspice::pwl(self.waveform(|(vinp, _)| *vinp)?),
+            Ngspice::pwl(self.waveform(|(_, vinn)| *vinn)?),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+impl<T: Dut> SaveTb<Spectre, Tran, ComparatorSim> for StrongArmMultiCycleTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Spectre>,
+        cell: &Cell<Self>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Spectre, Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> SaveTb<Ngspice, ngspice::tran::Tran, ComparatorSim> for StrongArmMultiCycleTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Ngspice>,
+        cell: &Cell<Self>,
+        opts: &mut <Ngspice as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Ngspice, ngspice::tran::Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+impl<T: Dut> Testbench<Spectre> for StrongArmMultiCycleTb<T> {
+    /// The outcome of each cycle.
+    type Output = Vec<ComparatorOutcome>;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        let mut opts = spectre::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        let wav = sim.simulate(
+            opts,
+            Tran {
+                stop: self.stop(),
+                start: None,
+                errpreset: Some(self.stimulus.errpreset),
+                noisefmax: None,
+                noisefmin: None,
+            },
+        );
+
+        self.outcomes(wav)
+    }
+}
+
+impl<T: Dut> Testbench<Ngspice> for StrongArmMultiCycleTb<T> {
+    /// The outcome of each cycle.
+    type Output = Vec<ComparatorOutcome>;
+
+    fn run(&self, sim: SimController<Ngspice, Self>) -> Self::Output {
+        let mut opts = ngspice::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        let wav = sim.simulate(
+            opts,
+            ngspice::tran::Tran {
+                step: self.stimulus.step,
+                stop: self.stop(),
+                start: None,
+            },
+        );
+
+        self.outcomes(wav)
+    }
+}
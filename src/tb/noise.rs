@@ -0,0 +1,212 @@
+//! Input-referred noise estimation using transient noise.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use spectre::analysis::tran::Tran;
+use spectre::blocks::Vsource;
+use spectre::Spectre;
+use substrate::block::Block;
+use substrate::io::schematic::HardwareType;
+use substrate::io::TestbenchIo;
+use substrate::pdk::corner::Pvt;
+use substrate::schematic::{Cell, CellBuilder, ExportsNestedData, Schematic};
+use substrate::simulation::data::{FromSaved, Save, SaveTb};
+use substrate::simulation::options::Temperature;
+use substrate::simulation::{SimController, SimulationContext, Simulator, Testbench};
+
+use super::{
+    tb_schematic, ComparatorDecision, ComparatorOutcome, ComparatorSim, DecisionTolerance, Dut,
+    MeasurementError, StrongArmStimulus, StrongArmTranTbNodes,
+};
+
+/// A testbench that estimates the input-referred noise of a comparator.
+///
+/// Clocks the comparator for `cycles` periods with transient noise enabled and
+/// a small fixed differential input, then fits a zero-mean Gaussian CDF to the
+/// fraction of positive decisions.
+///
+/// The fit assumes that the comparator has no offset. Any offset shifts the
+/// fraction of positive decisions and biases the estimate.
+/// Only supported in Spectre.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "TestbenchIo")]
+pub struct StrongArmNoiseTb<T> {
+    pub dut: T,
+    pub vinp: Decimal,
+    pub vinn: Decimal,
+    pub pvt: Pvt<Sky130Corner>,
+    /// The clock and simulation settings.
+    ///
+    /// The stop time is ignored; the simulation ends `cycles` clock periods
+    /// after the first rising edge of the clock.
+    pub stimulus: StrongArmStimulus,
+    pub tolerance: DecisionTolerance,
+    /// The number of clock cycles to simulate.
+    pub cycles: usize,
+    /// The maximum frequency of the simulated device noise.
+    pub noisefmax: Decimal,
+}
+
+impl<T: Dut> ExportsNestedData for StrongArmNoiseTb<T> {
+    type NestedData = StrongArmTranTbNodes;
+}
+
+impl<T: Dut> Schematic<Spectre> for StrongArmNoiseTb<T> {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Spectre>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        tb_schematic(
+            &self.dut,
+            Vsource::dc(self.vinp),
+            Vsource::dc(self.vinn),
+            self.pvt.voltage,
+            &self.stimulus,
+            io,
+            cell,
+        )
+    }
+}
+
+impl<T: Dut> SaveTb<Spectre, Tran, ComparatorSim> for StrongArmNoiseTb<T> {
+    fn save_tb(
+        ctx: &SimulationContext<Spectre>,
+        cell: &Cell<Self>,
+        opts: &mut <Spectre as Simulator>::Options,
+    ) -> <ComparatorSim as FromSaved<Spectre, Tran>>::SavedKey {
+        ComparatorSim::save(ctx, cell.data(), opts)
+    }
+}
+
+/// The result of a transient noise simulation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoiseEstimate {
+    /// The number of positive decisions.
+    pub pos: usize,
+    /// The number of negative decisions.
+    pub neg: usize,
+    /// The number of cycles in which the comparator did not resolve.
+    pub unresolved: usize,
+    /// The estimated input-referred noise standard deviation, in volts.
+    ///
+    /// [`None`] if every resolved decision was the same, in which case the
+    /// differential input is too large relative to the noise to estimate it.
+    /// Only meaningful if the comparator has no offset.
+    pub sigma: Option<f64>,
+}
+
+impl NoiseEstimate {
+    /// Estimates the input-referred noise from the outcomes of each cycle
+    /// simulated with differential input `vdiff`.
+    ///
+    /// Assumes the comparator has no offset, so that the probability of a positive
+    /// decision is `Phi(vdiff / sigma)`. Outputs that separate without railing
+    /// are counted as a decision in the direction of the separation.
+    pub fn from_outcomes(
+        vdiff: f64,
+        outcomes: impl IntoIterator<Item = ComparatorOutcome>,
+    ) -> Self {
+        let mut estimate = Self::default();
+        for outcome in outcomes {
+            match outcome.likely_decision() {
+                Some(ComparatorDecision::Pos) => estimate.pos += 1,
+                Some(ComparatorDecision::Neg) => estimate.neg += 1,
+                None => estimate.unresolved += 1,
+            }
+        }
+
+        if estimate.pos > 0 && estimate.neg > 0 {
+            let p = estimate.pos as f64 / (estimate.pos + estimate.neg) as f64;
+            let z = inverse_normal_cdf(p);
+            if z != 0.0 {
+                estimate.sigma = Some(vdiff / z);
+            }
+        }
+
+        estimate
+    }
+}
+
+impl<T: Dut> Testbench<Spectre> for StrongArmNoiseTb<T> {
+    /// The noise estimate, or an error if the simulator failed.
+    type Output = Result<NoiseEstimate, MeasurementError>;
+
+    fn run(&self, sim: SimController<Spectre, Self>) -> Self::Output {
+        let mut opts = spectre::Options::default();
+        sim.set_option(self.pvt.corner, &mut opts);
+        sim.set_option(Temperature::from(self.pvt.temp), &mut opts);
+        let wav: ComparatorSim = sim
+            .simulate(
+                opts,
+                Tran {
+                    stop: self.stimulus.delay + self.stimulus.period * Decimal::from(self.cycles),
+                    start: None,
+                    errpreset: Some(self.stimulus.errpreset),
+                    noisefmax: Some(self.noisefmax),
+                    noisefmin: None,
+                },
+            )
+            .map_err(|err| MeasurementError::SimulationError(err.to_string()))?;
+
+        Ok(NoiseEstimate::from_outcomes(
+            (self.vinp - self.vinn).to_f64().unwrap(),
+            wav.outcomes(self.pvt.voltage.to_f64().unwrap(), &self.tolerance),
+        ))
+    }
+}
+
+/// Returns the inverse of the standard normal CDF at probability `p`.
+///
+/// Uses the rational approximation by Peter Acklam, which has a relative
+/// error of less than `1.15e-9`.
+pub(crate) fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.383577518672690e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
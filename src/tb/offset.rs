@@ -0,0 +1,272 @@
+//! Input-referred offset measurement.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use sky130pdk::Sky130Pdk;
+use substrate::context::PdkContext;
+use substrate::pdk::corner::Pvt;
+use substrate::simulation::{Simulator, Testbench};
+
+use crate::InvalidParams;
+
+use super::{
+    ComparatorDecision, ComparatorOutcome, DecisionTolerance, Dut, MeasurementError,
+    StrongArmStimulus, StrongArmTranTb,
+};
+
+/// Parameters of a binary search for the input-referred offset of a comparator.
+///
+/// The offset is defined as the differential input voltage `vinp - vinn`
+/// at which the comparator decision flips.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct OffsetSearch {
+    /// The input common-mode voltage.
+    pub vcm: Decimal,
+    /// The largest offset magnitude that will be searched for.
+    ///
+    /// Must be positive.
+    pub max_offset: Decimal,
+    /// The resolution to which the offset is found.
+    ///
+    /// Must be positive. Resolutions finer than the precision of [`Decimal`]
+    /// end the search once the bisection stops making progress.
+    pub resolution: Decimal,
+}
+
+impl OffsetSearch {
+    /// Returns an error if `max_offset` or `resolution` is not positive.
+    pub fn validate(&self) -> Result<(), InvalidParams> {
+        if self.max_offset <= dec!(0) {
+            return Err(InvalidParams(format!(
+                "offset search range must be positive, got {}",
+                self.max_offset
+            )));
+        }
+        if self.resolution <= dec!(0) {
+            return Err(InvalidParams(format!(
+                "offset search resolution must be positive, got {}",
+                self.resolution
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the positive and negative input voltages for the differential input `vdiff`.
+    pub fn inputs(&self, vdiff: Decimal) -> (Decimal, Decimal) {
+        (self.vcm + vdiff / dec!(2), self.vcm - vdiff / dec!(2))
+    }
+
+    /// Runs the search using `simulate` to obtain the comparator outcome
+    /// for a given `(vinp, vinn)`.
+    ///
+    /// Returns [`None`] if the offset lies outside of `[-max_offset, max_offset]`.
+    ///
+    /// Outputs that separate without railing are treated as a decision in the
+    /// direction of the separation. If the comparator is metastable during the
+    /// search, the input is assumed to be within the comparator's metastability
+    /// window and the current input is returned as the offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search parameters are invalid or a simulation fails.
+    pub fn run(
+        &self,
+        simulate: impl FnMut(Decimal, Decimal) -> ComparatorOutcome,
+    ) -> Option<Decimal> {
+        self.try_run(simulate)
+            .unwrap_or_else(|err| panic!("failed to run offset search: {err}"))
+    }
+
+    /// Runs the search as in [`OffsetSearch::run`], returning an error if the search
+    /// parameters are invalid or a simulation fails instead of panicking.
+    pub fn try_run(
+        &self,
+        mut simulate: impl FnMut(Decimal, Decimal) -> ComparatorOutcome,
+    ) -> Result<Option<Decimal>, MeasurementError> {
+        let offsets = self.try_run_many(1, |vinp, vinn| vec![simulate(vinp, vinn)])?;
+        offsets.into_iter().next().unwrap()
+    }
+
+    /// Runs `n` searches in lockstep, using `simulate` to obtain the outcome of
+    /// every search for a given `(vinp, vinn)`.
+    ///
+    /// `simulate` must return one outcome per search. Searches that require the
+    /// same input share a single call to `simulate`. Returns the offset found by
+    /// each search, as described in [`OffsetSearch::run`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search parameters are invalid, if a simulation fails, or if
+    /// `simulate` returns the wrong number of outcomes.
+    pub fn run_many(
+        &self,
+        n: usize,
+        simulate: impl FnMut(Decimal, Decimal) -> Vec<ComparatorOutcome>,
+    ) -> Vec<Option<Decimal>> {
+        self.try_run_many(n, simulate)
+            .and_then(|offsets| offsets.into_iter().collect())
+            .unwrap_or_else(|err| panic!("failed to run offset search: {err}"))
+    }
+
+    /// Runs the searches as in [`OffsetSearch::run_many`], returning an error for each
+    /// search whose simulations fail instead of panicking.
+    ///
+    /// A failed simulation only ends the searches that observed the failure; the
+    /// remaining searches continue. Returns an error if the search parameters are
+    /// invalid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `simulate` returns the wrong number of outcomes.
+    pub fn try_run_many(
+        &self,
+        n: usize,
+        mut simulate: impl FnMut(Decimal, Decimal) -> Vec<ComparatorOutcome>,
+    ) -> Result<Vec<Result<Option<Decimal>, MeasurementError>>, MeasurementError> {
+        self.validate()?;
+
+        let mut decide = |vdiff: Decimal| {
+            let (vinp, vinn) = self.inputs(vdiff);
+            let outcomes = simulate(vinp, vinn);
+            assert_eq!(outcomes.len(), n, "expected one outcome per search");
+            outcomes
+                .into_iter()
+                .map(|outcome| match outcome {
+                    ComparatorOutcome::SimulationError(err) => Err(err),
+                    outcome => Ok(outcome.likely_decision()),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let lo = decide(-self.max_offset);
+        let hi = decide(self.max_offset);
+        let mut searches = lo
+            .into_iter()
+            .zip(hi)
+            .map(|(lo, hi)| match (lo, hi) {
+                (Err(err), _) | (_, Err(err)) => SearchState::Failed(err),
+                (Ok(Some(ComparatorDecision::Neg)), Ok(Some(ComparatorDecision::Pos))) => {
+                    SearchState::Bisecting {
+                        lo: -self.max_offset,
+                        hi: self.max_offset,
+                    }
+                }
+                _ => SearchState::Done(None),
+            })
+            .collect::<Vec<_>>();
+
+        loop {
+            let mids = searches
+                .iter()
+                .filter_map(|search| search.mid(self.resolution))
+                .collect::<BTreeSet<_>>();
+            if mids.is_empty() {
+                break;
+            }
+            for mid in mids {
+                let decisions = decide(mid);
+                for (search, decision) in searches.iter_mut().zip(decisions) {
+                    if search.mid(self.resolution) == Some(mid) {
+                        search.update(mid, decision);
+                    }
+                }
+            }
+        }
+
+        Ok(searches.into_iter().map(SearchState::offset).collect())
+    }
+}
+
+/// The state of a single binary search for an offset.
+#[derive(Clone, Debug)]
+enum SearchState {
+    /// The offset lies between `lo` and `hi`.
+    Bisecting { lo: Decimal, hi: Decimal },
+    /// The search has finished.
+    Done(Option<Decimal>),
+    /// A simulation required by the search failed.
+    Failed(String),
+}
+
+impl SearchState {
+    /// The next input to simulate, or [`None`] if the search has finished,
+    /// reached `resolution`, or can no longer be bisected at [`Decimal`] precision.
+    fn mid(&self, resolution: Decimal) -> Option<Decimal> {
+        match *self {
+            Self::Bisecting { lo, hi } if hi - lo > resolution => {
+                let mid = (lo + hi) / dec!(2);
+                (mid != lo && mid != hi).then_some(mid)
+            }
+            _ => None,
+        }
+    }
+
+    /// Narrows the search using the comparator decision at input `mid`.
+    fn update(&mut self, mid: Decimal, decision: Result<Option<ComparatorDecision>, String>) {
+        if let Self::Bisecting { lo, hi } = *self {
+            *self = match decision {
+                Ok(Some(ComparatorDecision::Neg)) => Self::Bisecting { lo: mid, hi },
+                Ok(Some(ComparatorDecision::Pos)) => Self::Bisecting { lo, hi: mid },
+                Ok(None) => Self::Done(Some(mid)),
+                Err(err) => Self::Failed(err),
+            };
+        }
+    }
+
+    /// The offset found by the search.
+    fn offset(self) -> Result<Option<Decimal>, MeasurementError> {
+        match self {
+            Self::Bisecting { lo, hi } => Ok(Some((lo + hi) / dec!(2))),
+            Self::Done(offset) => Ok(offset),
+            Self::Failed(err) => Err(MeasurementError::SimulationError(err)),
+        }
+    }
+}
+
+/// A testbench that finds the input-referred offset of a comparator.
+///
+/// Runs a binary search over [`StrongArmTranTb`] simulations.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StrongArmOffsetTb<T> {
+    pub dut: T,
+    pub pvt: Pvt<Sky130Corner>,
+    pub stimulus: StrongArmStimulus,
+    pub tolerance: DecisionTolerance,
+    pub search: OffsetSearch,
+}
+
+impl<T: Dut> StrongArmOffsetTb<T> {
+    /// Returns the input-referred offset of the comparator, as simulated by `S`.
+    ///
+    /// Returns [`None`] if the offset lies outside of the search range, or an error
+    /// if the search parameters are invalid or a simulation fails.
+    pub fn run<S>(
+        &self,
+        ctx: &PdkContext<Sky130Pdk>,
+        work_dir: impl AsRef<Path>,
+    ) -> Result<Option<Decimal>, MeasurementError>
+    where
+        S: Simulator,
+        StrongArmTranTb<T>: Testbench<S, Output = ComparatorOutcome>,
+    {
+        self.search.try_run(|vinp, vinn| {
+            ctx.simulate::<S, _>(
+                StrongArmTranTb {
+                    dut: self.dut.clone(),
+                    vinp,
+                    vinn,
+                    pvt: self.pvt,
+                    stimulus: self.stimulus,
+                    tolerance: self.tolerance,
+                },
+                work_dir.as_ref(),
+            )
+            .unwrap_or_else(|err| ComparatorOutcome::SimulationError(err.to_string()))
+        })
+    }
+}
@@ -0,0 +1,234 @@
+//! Sweeps of comparator performance across process, voltage, and temperature.
+
+use std::fmt;
+use std::path::Path;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sky130pdk::corner::Sky130Corner;
+use sky130pdk::Sky130Pdk;
+use substrate::context::PdkContext;
+use substrate::pdk::corner::Pvt;
+use substrate::simulation::{Simulator, Testbench};
+
+use super::offset::{OffsetSearch, StrongArmOffsetTb};
+use super::{
+    ComparatorOutcome, DecisionThreshold, DecisionTolerance, DelayMeasurement, Dut,
+    StrongArmDelayTb, StrongArmStimulus, StrongArmTranTb,
+};
+
+/// The five standard Sky130 process corners.
+pub const SKY130_CORNERS: [Sky130Corner; 5] = [
+    Sky130Corner::Tt,
+    Sky130Corner::Ss,
+    Sky130Corner::Ff,
+    Sky130Corner::Sf,
+    Sky130Corner::Fs,
+];
+
+/// A sweep of comparator decision, delay, and offset over a grid of PVT conditions.
+///
+/// Every combination of `corners`, `voltages`, and `temps` is simulated.
+/// At each PVT, the comparator is driven with a differential input of `overdrive`
+/// around the common mode of `search` to measure the decision and delay,
+/// and `search` is run to measure the input-referred offset.
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StrongArmPvtSweep<T> {
+    pub dut: T,
+    pub corners: Vec<Sky130Corner>,
+    /// The supply voltages to sweep.
+    pub voltages: Vec<Decimal>,
+    /// The temperatures to sweep, in degrees Celsius.
+    pub temps: Vec<Decimal>,
+    pub stimulus: StrongArmStimulus,
+    pub tolerance: DecisionTolerance,
+    /// The threshold used to measure the decision delay.
+    pub threshold: DecisionThreshold,
+    pub search: OffsetSearch,
+    /// The differential input `vinp - vinn` used to measure the decision and delay.
+    pub overdrive: Decimal,
+}
+
+/// The measurements taken at a single PVT.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PvtMeasurements {
+    /// The comparator outcome with a differential input of the sweep overdrive.
+    pub outcome: ComparatorOutcome,
+    /// The decision delay, in seconds.
+    pub delay: Option<f64>,
+    /// The input-referred offset, or [`None`] if it lies outside of the search range.
+    pub offset: Option<Decimal>,
+}
+
+/// The results of a [`StrongArmPvtSweep`].
+///
+/// The measurements form a grid indexed by corner, then voltage, then temperature.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PvtReport {
+    pub corners: Vec<Sky130Corner>,
+    /// The swept supply voltages.
+    pub voltages: Vec<Decimal>,
+    /// The swept temperatures, in degrees Celsius.
+    pub temps: Vec<Decimal>,
+    measurements: Vec<PvtMeasurements>,
+}
+
+impl PvtReport {
+    /// Returns the measurements taken at `pvt`, if it was part of the sweep.
+    pub fn get(&self, pvt: &Pvt<Sky130Corner>) -> Option<&PvtMeasurements> {
+        let corner = self.corners.iter().position(|&c| c == pvt.corner)?;
+        let voltage = self.voltages.iter().position(|&v| v == pvt.voltage)?;
+        let temp = self.temps.iter().position(|&t| t == pvt.temp)?;
+        self.measurements
+            .get((corner * self.voltages.len() + voltage) * self.temps.len() + temp)
+    }
+
+    /// Returns the number of PVTs in the report.
+    pub fn len(&self) -> usize {
+        self.measurements.len()
+    }
+
+    /// Returns `true` if the report contains no PVTs.
+    pub fn is_empty(&self) -> bool {
+        self.measurements.is_empty()
+    }
+
+    /// Returns an iterator over every PVT in the report and its measurements,
+    /// ordered by corner, then voltage, then temperature.
+    pub fn iter(&self) -> impl Iterator<Item = (Pvt<Sky130Corner>, &PvtMeasurements)> {
+        pvt_grid(&self.corners, &self.voltages, &self.temps)
+            .into_iter()
+            .zip(&self.measurements)
+    }
+}
+
+/// Returns every combination of `corners`, `voltages`, and `temps`,
+/// ordered by corner, then voltage, then temperature.
+fn pvt_grid(
+    corners: &[Sky130Corner],
+    voltages: &[Decimal],
+    temps: &[Decimal],
+) -> Vec<Pvt<Sky130Corner>> {
+    let mut pvts = Vec::new();
+    for &corner in corners {
+        for &voltage in voltages {
+            for &temp in temps {
+                pvts.push(Pvt {
+                    corner,
+                    voltage,
+                    temp,
+                });
+            }
+        }
+    }
+    pvts
+}
+
+impl fmt::Display for PvtReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<8} {:>8} {:>8} {:>10} {:>12} {:>12}",
+            "corner", "vdd (V)", "temp (C)", "decision", "delay (ps)", "offset (mV)"
+        )?;
+        for (pvt, measurements) in self.iter() {
+            let decision = match measurements.outcome.decision() {
+                Some(decision) => format!("{decision:?}"),
+                None => "-".to_string(),
+            };
+            let delay = match measurements.delay {
+                Some(delay) => format!("{:.1}", delay * 1e12),
+                None => "-".to_string(),
+            };
+            let offset = match measurements.offset {
+                Some(offset) => format!("{:.2}", offset * Decimal::ONE_THOUSAND),
+                None => "-".to_string(),
+            };
+            writeln!(
+                f,
+                "{:<8} {:>8} {:>8} {:>10} {:>12} {:>12}",
+                format!("{:?}", pvt.corner),
+                pvt.voltage,
+                pvt.temp,
+                decision,
+                delay,
+                offset
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Dut> StrongArmPvtSweep<T> {
+    /// Returns every PVT in the sweep, in the order they are simulated.
+    pub fn pvts(&self) -> Vec<Pvt<Sky130Corner>> {
+        pvt_grid(&self.corners, &self.voltages, &self.temps)
+    }
+
+    /// Runs the sweep using simulator `S`.
+    ///
+    /// The simulations for each PVT are placed in their own subdirectory of `work_dir`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset search parameters are invalid or a simulation fails.
+    pub fn run<S>(&self, ctx: &PdkContext<Sky130Pdk>, work_dir: impl AsRef<Path>) -> PvtReport
+    where
+        S: Simulator,
+        StrongArmTranTb<T>: Testbench<S, Output = ComparatorOutcome>,
+        StrongArmDelayTb<T>: Testbench<S, Output = DelayMeasurement>,
+    {
+        let measurements = self
+            .pvts()
+            .into_iter()
+            .map(|pvt| {
+                let work_dir = work_dir
+                    .as_ref()
+                    .join(format!("{:?}_{}v_{}c", pvt.corner, pvt.voltage, pvt.temp));
+                let (vinp, vinn) = self.search.inputs(self.overdrive);
+
+                let DelayMeasurement { outcome, delay } = ctx
+                    .simulate::<S, _>(
+                        StrongArmDelayTb {
+                            tb: StrongArmTranTb {
+                                dut: self.dut.clone(),
+                                vinp,
+                                vinn,
+                                pvt,
+                                stimulus: self.stimulus,
+                                tolerance: self.tolerance,
+                            },
+                            threshold: self.threshold,
+                        },
+                        work_dir.join("decision"),
+                    )
+                    .expect("failed to run simulation");
+                if let ComparatorOutcome::SimulationError(err) = &outcome {
+                    panic!("failed to run simulation: {err}");
+                }
+                let offset = StrongArmOffsetTb {
+                    dut: self.dut.clone(),
+                    pvt,
+                    stimulus: self.stimulus,
+                    tolerance: self.tolerance,
+                    search: self.search,
+                }
+                .run::<S>(ctx, work_dir.join("offset"))
+                .unwrap_or_else(|err| panic!("failed to measure offset: {err}"));
+
+                PvtMeasurements {
+                    outcome,
+                    delay,
+                    offset,
+                }
+            })
+            .collect();
+
+        PvtReport {
+            corners: self.corners.clone(),
+            voltages: self.voltages.clone(),
+            temps: self.temps.clone(),
+            measurements,
+        }
+    }
+}
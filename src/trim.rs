@@ -0,0 +1,367 @@
+//! Offset trim circuits.
+
+use serde::{Deserialize, Serialize};
+use sky130pdk::mos::{MosParams, Nfet01v8};
+use sky130pdk::Sky130Pdk;
+use substrate::arcstr::{self, ArcStr};
+use substrate::block::Block;
+use substrate::io::schematic::{Bundle, HardwareType, Node};
+use substrate::io::{Array, DiffPair, InOut, Input, Io, MosIoSchematic, Output, Signal};
+use substrate::schematic::{CellBuilder, ExportsNestedData, Schematic};
+
+use crate::{instantiate_mos, ClockedDiffComparatorIo, InvalidParams, MosKind, StrongArmInstance};
+
+#[derive(Debug, Clone, Io)]
+pub struct CapTrimComparatorIo {
+    pub input: Input<DiffPair>,
+    pub output: Output<DiffPair>,
+    pub clock: Input<Signal>,
+    pub vdd: InOut<Signal>,
+    pub vss: InOut<Signal>,
+    /// The trim code of the DAC attached to `intp`, LSB first.
+    pub trim_p: Input<Array<Signal>>,
+    /// The trim code of the DAC attached to `intn`, LSB first.
+    pub trim_n: Input<Array<Signal>>,
+}
+
+impl CapTrimComparatorIo {
+    /// Creates a new IO with `bits`-bit trim codes.
+    pub fn new(bits: usize) -> Self {
+        Self {
+            input: Default::default(),
+            output: Default::default(),
+            clock: Default::default(),
+            vdd: Default::default(),
+            vss: Default::default(),
+            trim_p: Input(Array::new(bits, Signal)),
+            trim_n: Input(Array::new(bits, Signal)),
+        }
+    }
+}
+
+/// A binary-weighted capacitive trim DAC.
+///
+/// Bit `i` is an NMOS capacitor of width `2^i * unit.w` with its gate on the
+/// trimmed node and its source and drain on the code input. Setting a bit
+/// moves the capacitor out of inversion, reducing the capacitance on the node.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CapTrimDac {
+    /// The parameters of the least significant capacitor.
+    pub unit: MosParams,
+    /// The number of bits in the trim code.
+    ///
+    /// Generating the schematic fails if the width of the most significant
+    /// capacitor overflows.
+    pub bits: usize,
+}
+
+impl CapTrimDac {
+    /// Returns the width of the capacitor for bit `i`.
+    ///
+    /// Returns an error if the width overflows.
+    fn width(&self, i: usize) -> Result<i64, InvalidParams> {
+        u32::try_from(i)
+            .ok()
+            .and_then(|i| 2i64.checked_pow(i))
+            .and_then(|scale| self.unit.w.checked_mul(scale))
+            .ok_or_else(|| {
+                InvalidParams(format!(
+                    "the width of bit {i} of a {}-bit trim DAC with a unit width of {} overflows",
+                    self.bits, self.unit.w
+                ))
+            })
+    }
+
+    /// Instantiates the DAC on `node`, controlled by the bits of `code`.
+    fn instantiate(
+        &self,
+        cell: &mut CellBuilder<Sky130Pdk>,
+        node: Node,
+        code: impl IntoIterator<Item = Node>,
+        vss: Node,
+    ) -> Result<(), InvalidParams> {
+        for (i, bit) in code.into_iter().enumerate() {
+            cell.instantiate_connected(
+                Nfet01v8::new(MosParams {
+                    w: self.width(i)?,
+                    ..self.unit
+                }),
+                MosIoSchematic {
+                    d: bit,
+                    g: node,
+                    s: bit,
+                    b: vss,
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A [`StrongArmInstance`] with a [`CapTrimDac`] on each of its internal nodes.
+///
+/// Increasing `trim_n` speeds up the discharge of `intn`, which favors a
+/// positive decision and shifts the input-referred offset negative.
+/// Increasing `trim_p` has the opposite effect.
+///
+/// The DAC capacitors are NMOS devices that only load the internal nodes while
+/// those nodes are precharged high, so the comparator must have an NMOS input
+/// pair. Generating the schematic fails otherwise.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CapTrimStrongArmInstance {
+    pub strongarm: StrongArmInstance,
+    pub dac: CapTrimDac,
+}
+
+impl Block for CapTrimStrongArmInstance {
+    type Io = CapTrimComparatorIo;
+
+    fn id() -> ArcStr {
+        arcstr::literal!("cap_trim_strongarm_instance")
+    }
+
+    fn name(&self) -> ArcStr {
+        arcstr::format!("cap_trim_strongarm_instance_{}b", self.dac.bits)
+    }
+
+    fn io(&self) -> Self::Io {
+        CapTrimComparatorIo::new(self.dac.bits)
+    }
+}
+
+impl ExportsNestedData for CapTrimStrongArmInstance {
+    type NestedData = ();
+}
+
+impl Schematic<Sky130Pdk> for CapTrimStrongArmInstance {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        if self.strongarm.input_kind != MosKind::Nmos {
+            return Err(InvalidParams(
+                "capacitive trim requires a comparator with an NMOS input pair".to_string(),
+            )
+            .into());
+        }
+
+        let nodes = self.strongarm.instantiate_devices(
+            &Bundle::<ClockedDiffComparatorIo> {
+                input: io.input.clone(),
+                output: io.output.clone(),
+                clock: io.clock,
+                vdd: io.vdd,
+                vss: io.vss,
+            },
+            None,
+            cell,
+        );
+
+        self.dac.instantiate(
+            cell,
+            nodes.intn,
+            (0..self.dac.bits).map(|i| io.trim_n[i]),
+            io.vss,
+        )?;
+        self.dac.instantiate(
+            cell,
+            nodes.intp,
+            (0..self.dac.bits).map(|i| io.trim_p[i]),
+            io.vss,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// A [`CapTrimStrongArmInstance`] with its trim codes tied to fixed values.
+///
+/// Exposes a [`ClockedDiffComparatorIo`], so it can be used in any of
+/// the comparator testbenches.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "ClockedDiffComparatorIo")]
+pub struct FixedCapTrim {
+    pub comparator: CapTrimStrongArmInstance,
+    /// The code applied to `trim_p`.
+    ///
+    /// Must fit in the number of bits of the DAC.
+    pub code_p: u64,
+    /// The code applied to `trim_n`.
+    ///
+    /// Must fit in the number of bits of the DAC.
+    pub code_n: u64,
+}
+
+impl ExportsNestedData for FixedCapTrim {
+    type NestedData = ();
+}
+
+impl Schematic<Sky130Pdk> for FixedCapTrim {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let bits = self.comparator.dac.bits;
+        for (name, code) in [("code_p", self.code_p), ("code_n", self.code_n)] {
+            let excess = u32::try_from(bits)
+                .ok()
+                .and_then(|bits| code.checked_shr(bits))
+                .unwrap_or(0);
+            if excess != 0 {
+                return Err(InvalidParams(format!(
+                    "{name} {code} does not fit in a {bits}-bit trim DAC"
+                ))
+                .into());
+            }
+        }
+
+        let comparator = cell.instantiate(self.comparator);
+        cell.connect(comparator.io().input.p, io.input.p);
+        cell.connect(comparator.io().input.n, io.input.n);
+        cell.connect(comparator.io().output.p, io.output.p);
+        cell.connect(comparator.io().output.n, io.output.n);
+        cell.connect(comparator.io().clock, io.clock);
+        cell.connect(comparator.io().vdd, io.vdd);
+        cell.connect(comparator.io().vss, io.vss);
+
+        for i in 0..bits {
+            for (code, trim) in [
+                (self.code_p, comparator.io().trim_p[i]),
+                (self.code_n, comparator.io().trim_n[i]),
+            ] {
+                let bit = code.checked_shr(i as u32).unwrap_or(0) & 1;
+                cell.connect(trim, if bit == 1 { io.vdd } else { io.vss });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Io)]
+pub struct AuxTrimComparatorIo {
+    pub input: Input<DiffPair>,
+    pub output: Output<DiffPair>,
+    pub clock: Input<Signal>,
+    pub vdd: InOut<Signal>,
+    pub vss: InOut<Signal>,
+    /// The differential trim voltage applied to the auxiliary pair.
+    pub trim: Input<DiffPair>,
+}
+
+/// A [`StrongArmInstance`] with an auxiliary differential pair in parallel with its input pair.
+///
+/// The auxiliary pair injects a current into the internal nodes proportional to
+/// the differential trim voltage, shifting the input-referred offset by roughly
+/// `-(trim.p - trim.n) * gm_aux / gm_input`.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "AuxTrimComparatorIo")]
+pub struct AuxTrimStrongArmInstance {
+    pub strongarm: StrongArmInstance,
+    pub aux_pair: MosParams,
+}
+
+impl ExportsNestedData for AuxTrimStrongArmInstance {
+    type NestedData = ();
+}
+
+impl Schematic<Sky130Pdk> for AuxTrimStrongArmInstance {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let nodes = self.strongarm.instantiate_devices(
+            &Bundle::<ClockedDiffComparatorIo> {
+                input: io.input.clone(),
+                output: io.output.clone(),
+                clock: io.clock,
+                vdd: io.vdd,
+                vss: io.vss,
+            },
+            None,
+            cell,
+        );
+
+        let kind = self.strongarm.input_kind;
+        let body = match kind {
+            MosKind::Nmos => io.vss,
+            MosKind::Pmos => io.vdd,
+        };
+        for (d, g) in [(nodes.intn, io.trim.p), (nodes.intp, io.trim.n)] {
+            instantiate_mos(
+                cell,
+                kind,
+                self.aux_pair,
+                MosIoSchematic {
+                    d,
+                    g,
+                    s: nodes.tail,
+                    b: body,
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A supply rail.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Rail {
+    Vdd,
+    Vss,
+}
+
+/// An [`AuxTrimStrongArmInstance`] with its trim inputs tied to fixed rails.
+///
+/// Exposes a [`ClockedDiffComparatorIo`], so it can be used in any of
+/// the comparator testbenches. Tying `trim_p` to [`Rail::Vdd`] and `trim_n` to
+/// [`Rail::Vss`] shifts the input-referred offset negative, and vice versa.
+#[derive(Serialize, Deserialize, Block, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[substrate(io = "ClockedDiffComparatorIo")]
+pub struct FixedAuxTrim {
+    pub comparator: AuxTrimStrongArmInstance,
+    /// The rail tied to `trim.p`.
+    pub trim_p: Rail,
+    /// The rail tied to `trim.n`.
+    pub trim_n: Rail,
+}
+
+impl ExportsNestedData for FixedAuxTrim {
+    type NestedData = ();
+}
+
+impl Schematic<Sky130Pdk> for FixedAuxTrim {
+    fn schematic(
+        &self,
+        io: &<<Self as Block>::Io as HardwareType>::Bundle,
+        cell: &mut CellBuilder<Sky130Pdk>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let comparator = cell.instantiate(self.comparator);
+        cell.connect(comparator.io().input.p, io.input.p);
+        cell.connect(comparator.io().input.n, io.input.n);
+        cell.connect(comparator.io().output.p, io.output.p);
+        cell.connect(comparator.io().output.n, io.output.n);
+        cell.connect(comparator.io().clock, io.clock);
+        cell.connect(comparator.io().vdd, io.vdd);
+        cell.connect(comparator.io().vss, io.vss);
+
+        for (rail, trim) in [
+            (self.trim_p, comparator.io().trim.p),
+            (self.trim_n, comparator.io().trim.n),
+        ] {
+            cell.connect(
+                trim,
+                match rail {
+                    Rail::Vdd => io.vdd,
+                    Rail::Vss => io.vss,
+                },
+            );
+        }
+
+        Ok(())
+    }
+}